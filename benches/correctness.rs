@@ -0,0 +1,45 @@
+//! Benchmarks of `Correctness::check` and `Correctness::check_packed`, run with
+//! `cargo bench --bench correctness`.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use roget::{Correctness, Word};
+use std::hint::black_box;
+
+/// Ordinary pairs, followed by the pairs with repeated letters that make the yellow pass do the
+/// most work.
+const PAIRS: [(&Word, &Word); 8] = [
+    (b"cigar", b"roate"),
+    (b"rebut", b"crane"),
+    (b"sissy", b"slate"),
+    (b"moved", b"moved"),
+    (b"geese", b"eerie"),
+    (b"abbey", b"babes"),
+    (b"eerie", b"geese"),
+    (b"llama", b"allay"),
+];
+
+fn check(c: &mut Criterion) {
+    c.bench_function("check", |b| {
+        b.iter(|| {
+            for (answer, guess) in PAIRS {
+                black_box(Correctness::check(black_box(answer), black_box(guess)));
+            }
+        })
+    });
+}
+
+fn check_packed(c: &mut Criterion) {
+    c.bench_function("check_packed", |b| {
+        b.iter(|| {
+            for (answer, guess) in PAIRS {
+                black_box(Correctness::check_packed(
+                    black_box(answer),
+                    black_box(guess),
+                ));
+            }
+        })
+    });
+}
+
+criterion_group!(benches, check, check_packed);
+criterion_main!(benches);
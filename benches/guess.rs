@@ -0,0 +1,37 @@
+//! Benchmarks picking the opener with `Unoptimized`, the most expensive guess of any game, using
+//! the same word lists as `main.rs`. Every iteration takes about a second in release, so this
+//! only takes the fewest samples criterion allows, and still takes a few minutes. Run it on its
+//! own with `cargo bench --bench guess`.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use roget::algorithms::Unoptimized;
+use roget::{Guesser, Wordle};
+use std::hint::black_box;
+use std::time::Duration;
+
+const DICTIONARY: &str = include_str!("../dictionary.txt");
+const ANSWERS: &str = include_str!("../answers.txt");
+const JOINED: &str = include_str!("../joined.txt");
+
+fn opener(c: &mut Criterion) {
+    let (wordle, remaining) = Wordle::builder(JOINED)
+        .dictionary(DICTIONARY)
+        .answers(ANSWERS)
+        .build()
+        .expect("The word lists should load and agree with each other");
+
+    c.bench_function("opener", |b| {
+        b.iter(|| {
+            black_box(Unoptimized::new(wordle.get_dictionary(), remaining.clone()).guess(&[]));
+        })
+    });
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default()
+        .sample_size(10)
+        .measurement_time(Duration::from_secs(30));
+    targets = opener
+}
+criterion_main!(benches);
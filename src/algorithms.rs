@@ -1,2 +1,236 @@
+use crate::{Correctness, DictionaryWithCounts, PackedCorrectness, Word};
+use std::collections::BTreeMap;
+
+mod clairvoyant;
+mod decision_tree;
+mod expected_remaining;
+mod frequency;
+pub mod metrics;
+mod minimax;
+mod multi_unoptimized;
+mod optimal_offline;
+mod random;
+mod remaining;
+mod two_ply;
 mod unoptimized;
-pub use unoptimized::Unoptimized;
+pub use clairvoyant::Clairvoyant;
+pub use decision_tree::{DecisionTree, ParseTreeError, TreeGuesser};
+pub use expected_remaining::ExpectedRemaining;
+pub use frequency::FrequencyGuesser;
+pub use minimax::Minimax;
+pub use multi_unoptimized::MultiUnoptimized;
+pub use optimal_offline::OptimalOffline;
+pub use random::RandomGuesser;
+pub use two_ply::TwoPly;
+pub use unoptimized::{Candidate, EntropyCache, Prior, Unoptimized};
+
+/// What the guessers of this crate guess once no word they know of fits the past guesses, which
+/// only happens when the answer isn't one of the words they were told could be, or the masks
+/// contradict each other: the first of `words`, the words they guess from, alphabetically. The
+/// game then goes on, and is most likely lost, the same way on every run, instead of panicking.
+fn fallback<'w>(words: impl IntoIterator<Item = &'w Word>) -> &'w Word {
+    words
+        .into_iter()
+        .min()
+        .expect("A guesser has at least one word to guess")
+}
+
+/// Splits `remaining` into buckets by the mask that guessing `word` would produce if each of its
+/// words were the answer, where each bucket holds the sum of `weight(occurrence_count)` over its
+/// words. This is the distribution of masks that guessing `word` can result in.
+fn partition(
+    word: &Word,
+    remaining: &DictionaryWithCounts,
+    check: impl Fn(&Word, &Word) -> PackedCorrectness,
+    weight: impl Fn(f64) -> f64,
+) -> [f64; PackedCorrectness::COUNT] {
+    let mut buckets = [0.0; PackedCorrectness::COUNT];
+    partition_into(word, remaining, check, weight, &mut buckets);
+    buckets
+}
+
+/// Like `partition`, but fills `buckets`, which has to hold `PackedCorrectness::COUNT` entries,
+/// instead of returning a new array. This lets guessers that partition thousands of candidates
+/// per guess reuse one buffer for all of them. `remaining` can be anything that iterates over
+/// words and their counts like a `DictionaryWithCounts` does.
+fn partition_into<'a, 'w: 'a>(
+    word: &Word,
+    remaining: impl IntoIterator<Item = (&'a &'w Word, &'a f64)>,
+    check: impl Fn(&Word, &Word) -> PackedCorrectness,
+    weight: impl Fn(f64) -> f64,
+    buckets: &mut [f64],
+) {
+    buckets.fill(0.0);
+    for (future_answer, &future_occurrence_count) in remaining {
+        // A bucket represents the weight of all words that are possible answers given that a
+        // specific mask (index of the bucket) results.
+        buckets[check(future_answer, word).as_index()] += weight(future_occurrence_count);
+    }
+}
+
+/// Like `partition`, but keeps the words of each bucket alongside their counts instead of summing
+/// them up, for guessers that search through what's left after each mask. Only masks that some
+/// word produces get a bucket, and buckets come in the order of their masks.
+fn partition_words<'w>(
+    word: &Word,
+    remaining: &[(&'w Word, f64)],
+) -> BTreeMap<PackedCorrectness, Vec<(&'w Word, f64)>> {
+    let mut buckets: BTreeMap<_, Vec<_>> = BTreeMap::new();
+    for &(future_answer, future_occurrence_count) in remaining {
+        buckets
+            .entry(Correctness::check_packed(future_answer, word))
+            .or_default()
+            .push((future_answer, future_occurrence_count));
+    }
+
+    buckets
+}
+
+#[cfg(test)]
+mod tests {
+    mod play_wordle {
+        use crate::algorithms::{
+            Clairvoyant, ExpectedRemaining, FrequencyGuesser, Minimax, OptimalOffline,
+            RandomGuesser, TwoPly, Unoptimized,
+        };
+        use crate::{Dictionary, DictionaryWithCounts, GameRecord, Guess, Guesser, Word, Wordle};
+        use std::collections::HashSet;
+
+        const WORDS: [&Word; 6] = [b"hello", b"world", b"moved", b"which", b"crane", b"slate"];
+
+        /// Every guesser shipped in `algorithms` has to be playable through `Wordle::play`.
+        #[test]
+        fn every_guesser_plays() {
+            let wordle = Wordle::new(WORDS);
+            let remaining = DictionaryWithCounts::from_iter(WORDS.map(|word| (word, 1.0)));
+
+            for &answer in &WORDS {
+                assert!(wordle
+                    .play(
+                        answer,
+                        Unoptimized::new(wordle.get_dictionary(), remaining.clone())
+                    )
+                    .is_some());
+                assert!(wordle
+                    .play(answer, RandomGuesser::new(wordle.get_dictionary(), 1))
+                    .is_some());
+                assert!(wordle
+                    .play(answer, Minimax::new(remaining.clone()))
+                    .is_some());
+                assert!(wordle
+                    .play(answer, ExpectedRemaining::new(remaining.clone()))
+                    .is_some());
+                assert!(wordle
+                    .play(answer, FrequencyGuesser::new(remaining.clone()))
+                    .is_some());
+                assert!(wordle
+                    .play(
+                        answer,
+                        TwoPly::new(wordle.get_dictionary(), remaining.clone(), 20)
+                    )
+                    .is_some());
+                assert!(wordle
+                    .play(
+                        answer,
+                        OptimalOffline::new(wordle.get_dictionary(), remaining.clone())
+                    )
+                    .is_some());
+                assert_eq!(wordle.play(answer, Clairvoyant::new(answer)), Some(1));
+            }
+
+            // Reset between games, one guesser has to play every game like a new one would.
+            plays_like_new_after_reset(&wordle, || {
+                Unoptimized::new(wordle.get_dictionary(), remaining.clone())
+            });
+            plays_like_new_after_reset(&wordle, || Minimax::new(remaining.clone()));
+            plays_like_new_after_reset(&wordle, || ExpectedRemaining::new(remaining.clone()));
+            plays_like_new_after_reset(&wordle, || FrequencyGuesser::new(remaining.clone()));
+            plays_like_new_after_reset(&wordle, || {
+                TwoPly::new(wordle.get_dictionary(), remaining.clone(), 20)
+            });
+            plays_like_new_after_reset(&wordle, || {
+                OptimalOffline::new(wordle.get_dictionary(), remaining.clone())
+            });
+
+            // The random numbers carry on across games, so only the words it picks from start
+            // over.
+            let mut random = RandomGuesser::new(wordle.get_dictionary(), 1);
+            for _ in 0..10 {
+                for &answer in &WORDS {
+                    random.reset();
+                    assert!(wordle.play(answer, &mut random).is_some());
+                }
+            }
+        }
+
+        /// No word is left after a guess that rules all of them out, which takes every guesser to
+        /// its fallback, the first word alphabetically.
+        #[test]
+        fn every_guesser_falls_back_once_no_word_fits() {
+            let wordle = Wordle::new(WORDS);
+            let remaining = DictionaryWithCounts::from_iter(WORDS.map(|word| (word, 1.0)));
+            // Every word has an "e", an "h", an "l" or an "o".
+            let past = [Guess::new(b"jumpy", b"hello")];
+
+            let guesses = [
+                Unoptimized::new(wordle.get_dictionary(), remaining.clone()).guess(&past),
+                RandomGuesser::new(wordle.get_dictionary(), 1).guess(&past),
+                Minimax::new(remaining.clone()).guess(&past),
+                ExpectedRemaining::new(remaining.clone()).guess(&past),
+                FrequencyGuesser::new(remaining.clone()).guess(&past),
+                TwoPly::new(wordle.get_dictionary(), remaining.clone(), 20).guess(&past),
+                OptimalOffline::new(wordle.get_dictionary(), remaining.clone()).guess(&past),
+            ];
+            assert!(guesses.iter().all(|&guess| guess == b"crane"));
+
+            // The answer isn't one of the words the guesser was given, so the game is lost
+            // rather than the guesser running out of words to pick from.
+            let wordle = Wordle::new(WORDS.iter().copied().chain([b"jumpy" as &Word]));
+            assert_eq!(
+                wordle.play(b"jumpy", RandomGuesser::new(&Dictionary::from(WORDS), 1)),
+                None
+            );
+        }
+
+        fn plays_like_new_after_reset<'w, G: Guesser<'w>>(
+            wordle: &Wordle<'w>,
+            new: impl Fn() -> G,
+        ) {
+            let words = |record: GameRecord<'w>| {
+                Vec::from_iter(record.guesses.iter().map(|guess| guess.word))
+            };
+
+            let mut reused = new();
+            for &answer in &WORDS {
+                reused.reset();
+                assert_eq!(
+                    words(wordle.play_recorded(answer, &mut reused)),
+                    words(wordle.play_recorded(answer, new())),
+                    "{} played {:?} differently after a reset",
+                    reused.name(),
+                    std::str::from_utf8(answer).unwrap()
+                );
+            }
+        }
+
+        #[test]
+        fn every_guesser_has_its_own_name() {
+            let wordle = Wordle::new(WORDS);
+            let remaining = DictionaryWithCounts::from_iter(WORDS.map(|word| (word, 1.0)));
+
+            let names = [
+                Unoptimized::new(wordle.get_dictionary(), remaining.clone()).name(),
+                RandomGuesser::new(wordle.get_dictionary(), 1).name(),
+                Minimax::new(remaining.clone()).name(),
+                ExpectedRemaining::new(remaining.clone()).name(),
+                FrequencyGuesser::new(remaining.clone()).name(),
+                TwoPly::new(wordle.get_dictionary(), remaining.clone(), 20).name(),
+                OptimalOffline::new(wordle.get_dictionary(), remaining).name(),
+                Clairvoyant::new(WORDS[0]).name(),
+            ];
+
+            assert_eq!(HashSet::from(names).len(), names.len());
+            assert_eq!(names[0], "unoptimized");
+        }
+    }
+}
@@ -0,0 +1,43 @@
+use crate::{Guess, Guesser, Word};
+
+/// A guesser that cheats: it's told the answer up front and guesses it straight away. No guesser
+/// can do better than its average of 1, which makes it the floor to compare others against, and a
+/// guesser that's trivially correct for testing the game itself.
+pub struct Clairvoyant<'w> {
+    answer: &'w Word,
+}
+
+impl<'w> Clairvoyant<'w> {
+    /// Takes the answer of the game it's going to play.
+    pub fn new(answer: &'w Word) -> Self {
+        Self { answer }
+    }
+}
+
+impl<'w> Guesser<'w> for Clairvoyant<'w> {
+    fn guess(&mut self, _past_guesses: &[Guess<'w>]) -> &'w Word {
+        self.answer
+    }
+
+    fn name(&self) -> &'static str {
+        "clairvoyant"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod play_wordle {
+        use crate::algorithms::Clairvoyant;
+        use crate::{Word, Wordle};
+
+        #[test]
+        fn clairvoyant_guesses_the_answer_first() {
+            let words: [&Word; 3] = [b"hello", b"world", b"moved"];
+            let wordle = Wordle::new(words);
+
+            for answer in words {
+                assert_eq!(wordle.play(answer, Clairvoyant::new(answer)), Some(1));
+            }
+        }
+    }
+}
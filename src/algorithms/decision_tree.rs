@@ -0,0 +1,520 @@
+use crate::{
+    Correctness, Dictionary, Guess, Guesser, PackedCorrectness, ParseMaskError,
+    RepresentableAsWord, Word, WordDisplay, WordError, Wordle, WORD_SIZE,
+};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+/// What to guess for every mask that can come back, down to the last answer, so that playing a
+/// game is only a matter of following the masks. Every answer the tree was built for is counted
+/// the same.
+///
+/// Building a tree searches the whole game tree like `OptimalOffline` does, so it's only feasible
+/// for small sets of answers, but a tree that was built once can be written out with `Display`
+/// and read back with `DecisionTree::parse`, and then played by a `TreeGuesser` without any
+/// search at all.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecisionTree<'w> {
+    guess: &'w Word,
+    /// Whether `guess` is one of the answers that get here, which it then wins the game for.
+    wins: bool,
+    /// What to do next for every other mask `guess` can get.
+    branches: BTreeMap<PackedCorrectness, DecisionTree<'w>>,
+}
+
+impl<'w> DecisionTree<'w> {
+    /// Builds the tree that takes the fewest guesses on average to find every word of `answers`,
+    /// guessing from the dictionary of `wordle`. Between guesses that are just as good, a guess
+    /// that could be the answer comes first, then the one that comes first alphabetically.
+    ///
+    /// The search gives up on a guess as soon as the guesses it's sure to take add up to more than
+    /// the best guess found so far takes, which is what makes it feasible for more than a handful
+    /// of answers. It's still exponential, though.
+    pub fn build(wordle: &Wordle<'w>, answers: &[&'w Word]) -> Self {
+        let mut guesses = Vec::from_iter(wordle.get_dictionary().iter().copied());
+        guesses.sort_unstable();
+        let mut answers = answers.to_vec();
+        answers.sort_unstable();
+        answers.dedup();
+        assert!(!answers.is_empty(), "There has to be at least one answer");
+
+        let mut search = Search {
+            guesses,
+            solved: HashMap::new(),
+        };
+        search.tree(&answers)
+    }
+
+    /// The word to guess at this point of the game.
+    pub fn guess(&self) -> &'w Word {
+        self.guess
+    }
+
+    /// Where the game goes when guessing `guess` gets `mask`, or `None` if it's won, or if none of
+    /// the answers the tree was built for gets that mask.
+    pub fn next(&self, mask: &[Correctness; WORD_SIZE]) -> Option<&DecisionTree<'w>> {
+        self.branches.get(&PackedCorrectness::from(*mask))
+    }
+
+    /// How many answers the tree finds.
+    pub fn answers(&self) -> usize {
+        usize::from(self.wins) + self.branches.values().map(Self::answers).sum::<usize>()
+    }
+
+    /// How many guesses finding every answer takes, added up over the answers.
+    pub fn total_guesses(&self) -> usize {
+        // Every answer that gets here takes this guess, and the answers further down take theirs
+        // on top of it.
+        self.answers()
+            + self
+                .branches
+                .values()
+                .map(Self::total_guesses)
+                .sum::<usize>()
+    }
+
+    /// How many guesses finding an answer takes on average.
+    pub fn average_guesses(&self) -> f64 {
+        self.total_guesses() as f64 / self.answers() as f64
+    }
+
+    /// Reads a tree written out by `Display`, taking every word from `dictionary`.
+    pub fn parse(text: &str, dictionary: &Dictionary<'w>) -> Result<Self, ParseTreeError> {
+        let mut lines = text
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| {
+                let content = line.trim_start_matches(' ');
+                let indent = line.len() - content.len();
+                (
+                    i + 1,
+                    indent / INDENT,
+                    indent % INDENT == 0,
+                    content.trim_end(),
+                )
+            })
+            .peekable();
+
+        let Some((line, depth, aligned, root)) = lines.next() else {
+            return Err(ParseTreeError::Empty);
+        };
+        if depth != 0 || !aligned {
+            return Err(ParseTreeError::Indentation { line });
+        }
+        let guess = parse_word(root, line, dictionary)?;
+        let tree = parse_node(guess, 0, &mut lines, dictionary)?;
+
+        match lines.next() {
+            Some((line, ..)) => Err(ParseTreeError::Indentation { line }),
+            None => Ok(tree),
+        }
+    }
+
+    fn write_branches(&self, f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+        let indent = " ".repeat(INDENT * (depth + 1));
+        if self.wins {
+            writeln!(f, "{}{}", indent, WON)?;
+        }
+        for (mask, branch) in &self.branches {
+            writeln!(f, "{}{} {}", indent, mask, WordDisplay(branch.guess))?;
+            branch.write_branches(f, depth + 1)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// How many spaces every level of the tree is indented by more than the one above it.
+const INDENT: usize = 2;
+
+/// The line that marks a guess as winning the game.
+const WON: &str = "CCCCC";
+
+/// Writes the tree one guess per line, starting with the first guess. Below every guess, indented
+/// by one more level, come the masks it can get, each followed by the guess to make next, or just
+/// `CCCCC` if the guess can win:
+///
+/// ```text
+/// chjmx
+///   CWWWW cello
+///     CCCCC
+///   WMWWW hello
+///     CCCCC
+/// ```
+impl fmt::Display for DecisionTree<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", WordDisplay(self.guess))?;
+        self.write_branches(f, 0)
+    }
+}
+
+fn parse_word<'w>(
+    text: &str,
+    line: usize,
+    dictionary: &Dictionary<'w>,
+) -> Result<&'w Word, ParseTreeError> {
+    let word = text
+        .try_as_word()
+        .map_err(|error| ParseTreeError::Word { line, error })?;
+
+    dictionary
+        .get(word)
+        .copied()
+        .ok_or(ParseTreeError::UnknownWord { line })
+}
+
+/// Reads the masks below `guess`, which is at `depth`, until a line that isn't below it.
+fn parse_node<'a, 'w>(
+    guess: &'w Word,
+    depth: usize,
+    lines: &mut std::iter::Peekable<impl Iterator<Item = (usize, usize, bool, &'a str)>>,
+    dictionary: &Dictionary<'w>,
+) -> Result<DecisionTree<'w>, ParseTreeError> {
+    let mut tree = DecisionTree {
+        guess,
+        wins: false,
+        branches: BTreeMap::new(),
+    };
+
+    while let Some(&(line, line_depth, aligned, content)) = lines.peek() {
+        if line_depth <= depth {
+            break;
+        }
+        if line_depth != depth + 1 || !aligned {
+            return Err(ParseTreeError::Indentation { line });
+        }
+        lines.next();
+
+        let (mask, word) = content.split_once(' ').unwrap_or((content, ""));
+        let mask =
+            Correctness::parse_mask(mask).map_err(|error| ParseTreeError::Mask { line, error })?;
+        if mask == [Correctness::Correct; WORD_SIZE] {
+            if !word.trim().is_empty() {
+                return Err(ParseTreeError::GuessAfterWin { line });
+            }
+            tree.wins = true;
+            continue;
+        }
+
+        let next = parse_word(word.trim(), line, dictionary)?;
+        let branch = parse_node(next, depth + 1, lines, dictionary)?;
+        tree.branches.insert(PackedCorrectness::from(mask), branch);
+    }
+
+    Ok(tree)
+}
+
+/// Why `DecisionTree::parse` couldn't read a tree, with the line it failed on, counting from 1.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ParseTreeError {
+    /// There are no lines to read.
+    Empty,
+    /// The line isn't indented by one level more than the guess it's below.
+    Indentation { line: usize },
+    /// The word on the line isn't a valid word.
+    Word { line: usize, error: WordError },
+    /// The word on the line isn't in the dictionary.
+    UnknownWord { line: usize },
+    /// The mask on the line isn't a valid mask.
+    Mask { line: usize, error: ParseMaskError },
+    /// The line has a guess after the mask that wins the game.
+    GuessAfterWin { line: usize },
+}
+
+impl fmt::Display for ParseTreeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "the tree has no guesses"),
+            Self::Indentation { line } => write!(
+                f,
+                "line {}: expected {} more spaces than the guess above",
+                line, INDENT
+            ),
+            Self::Word { line, error } => write!(f, "line {}: {}", line, error),
+            Self::UnknownWord { line } => {
+                write!(f, "line {}: the word is not in the dictionary", line)
+            }
+            Self::Mask { line, error } => write!(f, "line {}: {}", line, error),
+            Self::GuessAfterWin { line } => {
+                write!(f, "line {}: nothing can be guessed after a win", line)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseTreeError {}
+
+/// The exhaustive search behind `DecisionTree::build`.
+struct Search<'w> {
+    /// Every word that can be guessed, alphabetically.
+    guesses: Vec<&'w Word>,
+    /// The fewest total guesses, and the guess to start with, for every set of words that was
+    /// searched through to the end.
+    solved: HashMap<Vec<&'w Word>, (usize, &'w Word)>,
+}
+
+impl<'w> Search<'w> {
+    fn tree(&mut self, words: &[&'w Word]) -> DecisionTree<'w> {
+        let (_, guess) = self
+            .solve(words, usize::MAX)
+            .expect("Nothing takes usize::MAX guesses");
+
+        let mut tree = DecisionTree {
+            guess,
+            wins: false,
+            branches: BTreeMap::new(),
+        };
+        for (mask, bucket) in buckets(guess, words) {
+            if bucket == [guess] {
+                tree.wins = true;
+            } else {
+                tree.branches.insert(mask, self.tree(&bucket));
+            }
+        }
+
+        tree
+    }
+
+    /// The fewest guesses, added up over `words`, that finding every one of them takes, and the
+    /// guess to start with, or `None` if that takes `limit` guesses or more. `words` has to be
+    /// sorted.
+    fn solve(&mut self, words: &[&'w Word], limit: usize) -> Option<(usize, &'w Word)> {
+        if let [word] = words {
+            return (1 < limit).then_some((1, *word));
+        }
+        if let Some(&(total, guess)) = self.solved.get(words) {
+            return (total < limit).then_some((total, guess));
+        }
+        if lower_bound(words.len()) >= limit {
+            return None;
+        }
+
+        // Words that could be the answer go first, so that they win ties.
+        let guesses = Vec::from_iter(
+            words
+                .iter()
+                .chain(
+                    self.guesses
+                        .iter()
+                        .filter(|guess| words.binary_search(guess).is_err()),
+                )
+                .copied(),
+        );
+
+        let mut best = None;
+        let mut limit = limit;
+        'guesses: for guess in guesses {
+            let buckets = buckets(guess, words);
+            // A guess that leaves every word together gets us nowhere.
+            if buckets.len() == 1 && buckets[0].1 != [guess] {
+                continue;
+            }
+
+            // Every word takes this guess, and every word that isn't found by it takes at least
+            // one more, and all but one of them at least two.
+            let mut bound = words.len()
+                + buckets
+                    .iter()
+                    .filter(|(_, bucket)| *bucket != [guess])
+                    .map(|(_, bucket)| lower_bound(bucket.len()))
+                    .sum::<usize>();
+            if bound >= limit {
+                continue;
+            }
+
+            let mut total = words.len();
+            for (_, bucket) in buckets.iter().filter(|(_, bucket)| *bucket != [guess]) {
+                bound -= lower_bound(bucket.len());
+                // What's left of the limit after the guesses every other bucket is sure to take.
+                match self.solve(bucket, limit - bound) {
+                    Some((guesses, _)) => {
+                        total += guesses;
+                        bound += guesses;
+                    }
+                    None => continue 'guesses,
+                }
+            }
+
+            best = Some((total, guess));
+            limit = total;
+        }
+
+        // Without a guess below the limit, all that's known is that there's none, which isn't
+        // worth remembering.
+        if let Some(best) = best {
+            self.solved.insert(words.to_vec(), best);
+        }
+
+        best
+    }
+}
+
+/// The fewest guesses that finding every one of `words` words can take, added up: one guess can
+/// find only one of them, and the rest take at least one more.
+fn lower_bound(words: usize) -> usize {
+    2 * words - 1
+}
+
+/// The words that get every mask when `guess` is guessed, with the masks in order and the words
+/// sorted like `words` is.
+fn buckets<'w>(guess: &'w Word, words: &[&'w Word]) -> Vec<(PackedCorrectness, Vec<&'w Word>)> {
+    let mut buckets: BTreeMap<_, Vec<_>> = BTreeMap::new();
+    for &word in words {
+        buckets
+            .entry(Correctness::check_packed(word, guess))
+            .or_default()
+            .push(word);
+    }
+
+    Vec::from_iter(buckets)
+}
+
+/// Plays a game by following a `DecisionTree` down by the masks its guesses get, which takes no
+/// searching or scoring, however big the tree is.
+///
+/// Panics when a mask leads off the tree, which only happens when the answer isn't one of the
+/// answers the tree was built for.
+#[derive(Debug, Clone)]
+pub struct TreeGuesser<'t> {
+    root: &'t DecisionTree<'t>,
+    node: &'t DecisionTree<'t>,
+}
+
+impl<'t> TreeGuesser<'t> {
+    pub fn new(tree: &'t DecisionTree<'t>) -> Self {
+        Self {
+            root: tree,
+            node: tree,
+        }
+    }
+}
+
+impl<'t> Guesser<'t> for TreeGuesser<'t> {
+    fn guess(&mut self, past_guesses: &[Guess<'t>]) -> &'t Word {
+        // Earlier guesses already moved us down the tree on earlier turns.
+        if let Some(last) = past_guesses.last() {
+            self.node = self.node.next(&last.mask).unwrap_or_else(|| {
+                panic!(
+                    "The tree has no guess after {}, so the answer isn't one it was built for",
+                    last
+                )
+            });
+        }
+
+        self.node.guess
+    }
+
+    fn name(&self) -> &'static str {
+        "decision-tree"
+    }
+
+    fn reset(&mut self) {
+        self.node = self.root;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod decision_tree {
+        use crate::algorithms::{DecisionTree, OptimalOffline, ParseTreeError, TreeGuesser};
+        use crate::{DictionaryWithCounts, Guesser, RepresentableAsWord, Word, Wordle};
+
+        const ELLOS: [&Word; 4] = [b"hello", b"jello", b"cello", b"mello"];
+
+        #[test]
+        fn splitting_every_word_apart_wins_in_two() {
+            // Guessing any of the -ello words leaves the other three together, while "chjmx"
+            // tells all of them apart, just like for `OptimalOffline`.
+            let wordle = Wordle::with_answers(
+                [b"chjmx"],
+                DictionaryWithCounts::from_iter(ELLOS.map(|word| (word, 1.0))),
+            );
+            let tree = DecisionTree::build(&wordle, &ELLOS);
+
+            assert_eq!(tree.guess(), b"chjmx");
+            assert_eq!(tree.answers(), 4);
+            assert_eq!(tree.total_guesses(), 8);
+            assert_eq!(
+                tree.to_string(),
+                "chjmx\n  CWWWW cello\n    CCCCC\n  WMWWW hello\n    CCCCC\n  \
+                 WWMWW jello\n    CCCCC\n  WWWMW mello\n    CCCCC\n"
+            );
+
+            for answer in ELLOS {
+                assert_eq!(wordle.play(answer, TreeGuesser::new(&tree)), Some(2));
+            }
+        }
+
+        #[test]
+        fn without_a_word_that_splits_them_answers_are_guessed_in_turn() {
+            // Every guess finds one word and leaves the rest together, so the best there is takes
+            // 1 + 2 + 3 + 4 guesses, guessing alphabetically.
+            let wordle = Wordle::new(ELLOS);
+            let tree = DecisionTree::build(&wordle, &ELLOS);
+
+            assert_eq!(tree.total_guesses(), 10);
+            assert_eq!(tree.average_guesses(), 2.5);
+            assert_eq!(tree.guess(), b"cello");
+        }
+
+        #[test]
+        fn builds_as_well_as_optimal_offline_plays() {
+            let words = Vec::from_iter(
+                include_str!("../../answers.txt")
+                    .split_ascii_whitespace()
+                    .step_by(97)
+                    .map(|word_str| word_str.as_word()),
+            );
+            let wordle = Wordle::new(words.iter().copied());
+            let tree = DecisionTree::build(&wordle, &words);
+            let mut optimal = OptimalOffline::new(
+                wordle.get_dictionary(),
+                wordle.get_possible_answers().clone(),
+            );
+
+            assert_eq!(tree.answers(), words.len());
+            assert!((tree.average_guesses() - optimal.expected_guesses()).abs() < 1e-9);
+
+            let mut guesser = TreeGuesser::new(&tree);
+            let mut total = 0;
+            for &answer in &words {
+                guesser.reset();
+                total += wordle.play(answer, &mut guesser).unwrap();
+            }
+            assert_eq!(total, tree.total_guesses());
+        }
+
+        #[test]
+        fn parses_what_it_writes() {
+            let wordle = Wordle::with_answers(
+                [b"chjmx"],
+                DictionaryWithCounts::from_iter(ELLOS.map(|word| (word, 1.0))),
+            );
+            let tree = DecisionTree::build(&wordle, &ELLOS);
+
+            let parsed = DecisionTree::parse(&tree.to_string(), wordle.get_dictionary());
+            assert_eq!(parsed, Ok(tree));
+        }
+
+        #[test]
+        fn parse_errors_have_the_line() {
+            let wordle = Wordle::new(ELLOS);
+            let parse = |text| DecisionTree::parse(text, wordle.get_dictionary());
+
+            assert_eq!(parse("\n"), Err(ParseTreeError::Empty));
+            assert_eq!(
+                parse("hello\n  CCCCC\n    WCCCC jello\n"),
+                Err(ParseTreeError::Indentation { line: 3 })
+            );
+            assert_eq!(
+                parse("hello\n  WCCCC world\n"),
+                Err(ParseTreeError::UnknownWord { line: 2 })
+            );
+            assert_eq!(
+                parse("hello\n  CCCCC jello\n"),
+                Err(ParseTreeError::GuessAfterWin { line: 2 })
+            );
+        }
+    }
+}
@@ -0,0 +1,111 @@
+use super::{fallback, partition};
+use crate::{Constraints, Correctness, DictionaryWithCounts, Guess, Guesser, Word};
+
+#[derive(Debug, Copy, Clone)]
+struct Candidate<'w> {
+    /// The word of this candidate.
+    word: &'w Word,
+
+    /// The count coming form the DictionaryWithCounts value parameter. This lets us know how
+    /// frequent this word is in the English language.
+    occurrence_count: f64,
+
+    /// The number of remaining words we expect to be left with after guessing this candidate.
+    expected_remaining: f64,
+}
+
+/// Guesses the word that leaves the fewest remaining words on average. A mask whose bucket holds
+/// `n` out of `total` words turns up with probability `n / total` and leaves `n` words, so the
+/// expected number of remaining words is `Σ (n / total)⋅n`.
+pub struct ExpectedRemaining<'w> {
+    initial_remaining: DictionaryWithCounts<'w>,
+    remaining: DictionaryWithCounts<'w>,
+}
+
+impl<'w> ExpectedRemaining<'w> {
+    /// Takes the words that could be the answer, which are also the words it guesses from.
+    pub fn new(remaining: DictionaryWithCounts<'w>) -> Self {
+        Self {
+            initial_remaining: remaining.clone(),
+            remaining,
+        }
+    }
+}
+
+impl<'w> Guesser<'w> for ExpectedRemaining<'w> {
+    fn guess(&mut self, past_guesses: &[Guess<'w>]) -> &'w Word {
+        if let Some(last) = past_guesses.last() {
+            // Just like `Unoptimized`, earlier guesses were already filtered out on earlier turns.
+            let constraints = Constraints::from_guesses(std::slice::from_ref(last));
+            self.remaining.retain(|word, _| constraints.matches(word));
+        }
+        if self.remaining.is_empty() {
+            return fallback(self.initial_remaining.keys().copied());
+        }
+
+        let mut best: Option<Candidate<'w>> = None;
+
+        let total = self.remaining.len() as f64;
+
+        for (&word, &occurrence_count) in &self.remaining {
+            let expected_remaining =
+                partition(word, &self.remaining, Correctness::check_packed, |_| 1.0)
+                    .iter()
+                    .map(|&bucket_size| bucket_size / total * bucket_size)
+                    .sum::<f64>();
+
+            // A new guess is better if no guess was previously made, or if the new guess is
+            // expected to leave fewer words, or the same amount but is more common.
+            if best.is_none()
+                || expected_remaining < best.unwrap().expected_remaining
+                || (expected_remaining == best.unwrap().expected_remaining
+                    && occurrence_count > best.unwrap().occurrence_count)
+            {
+                best = Some(Candidate {
+                    word,
+                    occurrence_count,
+                    expected_remaining,
+                });
+            }
+        }
+
+        let best = best.expect("There is at least one remaining word");
+
+        best.word
+    }
+
+    fn name(&self) -> &'static str {
+        "expected-remaining"
+    }
+
+    /// Goes back to the words that could be the answer before the first guess.
+    fn reset(&mut self) {
+        self.remaining.clone_from(&self.initial_remaining);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod play_wordle {
+        use crate::algorithms::ExpectedRemaining;
+        use crate::{DictionaryWithCounts, Guesser, Word};
+
+        #[test]
+        fn expected_remaining_picks_the_word_leaving_fewest_words() {
+            // Any of the -ello words is expected to leave (1 + 3⋅3 + 1) / 5 = 2.2 words, while
+            // "chjmx" tells all of them apart and always leaves one.
+            let words: [(&'static Word, f64); 5] = [
+                (b"hello", 100.0),
+                (b"jello", 90.0),
+                (b"cello", 80.0),
+                (b"mello", 70.0),
+                (b"chjmx", 1.0),
+            ];
+
+            let mut expected_remaining =
+                ExpectedRemaining::new(DictionaryWithCounts::from_iter(words));
+
+            assert_eq!(expected_remaining.guess(&[]), b"chjmx");
+        }
+    }
+}
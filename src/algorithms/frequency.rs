@@ -0,0 +1,118 @@
+use super::fallback;
+use crate::{positional_frequencies, Constraints, DictionaryWithCounts, Guess, Guesser, Word};
+
+#[derive(Debug, Copy, Clone)]
+struct Candidate<'w> {
+    /// The word of this candidate.
+    word: &'w Word,
+
+    /// The count coming form the DictionaryWithCounts value parameter. This lets us know how
+    /// frequent this word is in the English language.
+    occurrence_count: f64,
+
+    /// The sum, over every position, of how many remaining words have this candidate's letter in
+    /// that position.
+    coverage: usize,
+}
+
+/// Guesses the word whose letters are the most common in their positions among the remaining
+/// words, the way a person might, without looking at the masks a guess could produce. This is a
+/// baseline to compare the information-theoretic guessers against.
+pub struct FrequencyGuesser<'w> {
+    initial_remaining: DictionaryWithCounts<'w>,
+    remaining: DictionaryWithCounts<'w>,
+}
+
+impl<'w> FrequencyGuesser<'w> {
+    /// Takes the words that could be the answer, which are also the words it guesses from.
+    pub fn new(remaining: DictionaryWithCounts<'w>) -> Self {
+        Self {
+            initial_remaining: remaining.clone(),
+            remaining,
+        }
+    }
+}
+
+impl<'w> Guesser<'w> for FrequencyGuesser<'w> {
+    fn guess(&mut self, past_guesses: &[Guess<'w>]) -> &'w Word {
+        if let Some(last) = past_guesses.last() {
+            // Just like `Unoptimized`, earlier guesses were already filtered out on earlier turns.
+            let constraints = Constraints::from_guesses(std::slice::from_ref(last));
+            self.remaining.retain(|word, _| constraints.matches(word));
+        }
+        if self.remaining.is_empty() {
+            return fallback(self.initial_remaining.keys().copied());
+        }
+
+        let counts = positional_frequencies(self.remaining.keys());
+        let mut best: Option<Candidate<'w>> = None;
+
+        for (&word, &occurrence_count) in &self.remaining {
+            let coverage = word
+                .iter()
+                .zip(&counts)
+                .filter(|(letter, _)| letter.is_ascii_lowercase())
+                .map(|(&letter, counts)| counts[(letter - b'a') as usize] as usize)
+                .sum();
+
+            // A new guess is better if no guess was previously made, or if its letters are more
+            // common, or are just as common but the word itself is more common, or is just as
+            // common but comes first alphabetically.
+            let better = match best {
+                None => true,
+                Some(best) => {
+                    coverage > best.coverage
+                        || (coverage == best.coverage && occurrence_count > best.occurrence_count)
+                        || (coverage == best.coverage
+                            && occurrence_count == best.occurrence_count
+                            && word < best.word)
+                }
+            };
+            if better {
+                best = Some(Candidate {
+                    word,
+                    occurrence_count,
+                    coverage,
+                });
+            }
+        }
+
+        let best = best.expect("There is at least one remaining word");
+
+        best.word
+    }
+
+    fn name(&self) -> &'static str {
+        "frequency"
+    }
+
+    /// Goes back to the words that could be the answer before the first guess.
+    fn reset(&mut self) {
+        self.remaining.clone_from(&self.initial_remaining);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod play_wordle {
+        use crate::algorithms::FrequencyGuesser;
+        use crate::{DictionaryWithCounts, Guesser, Word};
+
+        #[test]
+        fn frequency_prefers_words_with_common_letters() {
+            // "sores" shares its letters with most of the other words, in the same positions,
+            // while "jumpy" shares almost nothing, even though it is far more common.
+            let words: [(&'static Word, f64); 5] = [
+                (b"sores", 1.0),
+                (b"cores", 1.0),
+                (b"bores", 1.0),
+                (b"sorts", 1.0),
+                (b"jumpy", 100.0),
+            ];
+
+            let mut guesser = FrequencyGuesser::new(DictionaryWithCounts::from_iter(words));
+
+            assert_eq!(guesser.guess(&[]), b"sores");
+        }
+    }
+}
@@ -0,0 +1,186 @@
+//! The ways `Unoptimized` can score a guess, given the distribution of masks it can result in.
+
+use crate::{Correctness, PackedCorrectness, WORD_SIZE};
+
+/// Scores a guess from `buckets`, the probability of every mask the guess can result in, where
+/// masks that no remaining word produces have a probability of 0. Higher scores are better.
+///
+/// Splitting a bucket in two should never make for a lower score. `Unoptimized` relies on that to
+/// skip scoring once at most two words remain, since any remaining word then splits them apart
+/// as finely as any guess can.
+pub trait ScoringMetric: Sync {
+    fn score(&self, buckets: &[f64]) -> f64;
+
+    /// Whether scores are bits of information, which `Unoptimized` only reports its scores as
+    /// when they are, see `Guesser::last_expected_information`. By default they aren't.
+    fn in_bits(&self) -> bool {
+        false
+    }
+}
+
+/// Shannon entropy, `-Σp⋅log2(p)`, which is how many bits of information the guess gives on
+/// average. This is what `Unoptimized` uses by default.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct Entropy;
+
+impl ScoringMetric for Entropy {
+    fn score(&self, buckets: &[f64]) -> f64 {
+        // Entropy is the expected value of information, where an expected value is defined to
+        // be `Σp(x)⋅x`, and information is defined to be `-log2(p(x))`.
+        // Entropy is a measure of the uniformity of a distribution, and the number of
+        // possibilities within it.
+        -buckets
+            .iter()
+            .filter(|&&probability| probability > 0.0)
+            .map(|&probability| probability * f64::log2(probability))
+            .sum::<f64>()
+    }
+
+    fn in_bits(&self) -> bool {
+        true
+    }
+}
+
+/// The expected share of the remaining words the guess leaves, `Σp²`, negated so that leaving
+/// fewer words scores higher.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct ExpectedRemaining;
+
+impl ScoringMetric for ExpectedRemaining {
+    fn score(&self, buckets: &[f64]) -> f64 {
+        -buckets
+            .iter()
+            .map(|&probability| probability * probability)
+            .sum::<f64>()
+    }
+}
+
+/// The share of the remaining words left by the worst mask the guess can result in, negated so
+/// that a smaller worst case scores higher. This is the minimax criterion.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct WorstCase;
+
+impl ScoringMetric for WorstCase {
+    fn score(&self, buckets: &[f64]) -> f64 {
+        -buckets.iter().copied().fold(0.0, f64::max)
+    }
+}
+
+/// The Gini impurity of the masks, `1 - Σp²`, which is the chance that two answers drawn at random
+/// give different masks. It ranks guesses much like `Entropy`, without taking a logarithm per
+/// mask.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct Gini;
+
+impl ScoringMetric for Gini {
+    fn score(&self, buckets: &[f64]) -> f64 {
+        1.0 - buckets
+            .iter()
+            .map(|&probability| probability * probability)
+            .sum::<f64>()
+    }
+}
+
+/// Roughly how many bits of information each guess after this one gives, which is what
+/// narrowing the answers of Wordle down from the opener in about 3.4 guesses takes.
+const BITS_PER_LATER_GUESS: f64 = 4.6;
+
+/// An estimate of how many guesses the game takes from here, including this one, negated so that
+/// fewer guesses score higher. The mask that wins takes no more guesses, and every other mask is
+/// assumed to take `1 + log2(n) / 4.6` more for the `n` words it leaves.
+///
+/// Buckets only hold probabilities, so `n` is worked out from the number of `answers` this was
+/// made with. That's exact for the opener, and overestimates later on, when fewer words remain.
+#[derive(Debug, Copy, Clone)]
+pub struct EstimatedGuesses {
+    answers: f64,
+}
+
+impl EstimatedGuesses {
+    /// Takes the number of words that could be the answer before the first guess.
+    pub fn new(answers: usize) -> Self {
+        Self {
+            answers: answers as f64,
+        }
+    }
+}
+
+impl ScoringMetric for EstimatedGuesses {
+    fn score(&self, buckets: &[f64]) -> f64 {
+        let won = PackedCorrectness::from([Correctness::Correct; WORD_SIZE]).as_index();
+        let later_guesses = buckets
+            .iter()
+            .enumerate()
+            .filter(|&(mask, &probability)| mask != won && probability > 0.0)
+            .map(|(_, &probability)| {
+                let words = (probability * self.answers).max(1.0);
+                probability * (1.0 + words.log2() / BITS_PER_LATER_GUESS)
+            })
+            .sum::<f64>();
+
+        -(1.0 + later_guesses)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod score {
+        use crate::algorithms::metrics::{
+            Entropy, EstimatedGuesses, ExpectedRemaining, Gini, ScoringMetric, WorstCase,
+        };
+        use crate::{Correctness, PackedCorrectness};
+
+        // The masks of "hello" among the four -ello words, and of "chjmx", which tells them apart.
+        const HELLO: [f64; 3] = [0.25, 0.75, 0.0];
+        const CHJMX: [f64; 4] = [0.25; 4];
+
+        #[test]
+        fn entropy_is_in_bits() {
+            let hello = -(0.25 * f64::log2(0.25) + 0.75 * f64::log2(0.75));
+            assert!((Entropy.score(&HELLO) - hello).abs() < 1e-12);
+            assert!((Entropy.score(&CHJMX) - 2.0).abs() < 1e-12);
+        }
+
+        #[test]
+        fn expected_remaining_is_the_sum_of_squares() {
+            assert!((ExpectedRemaining.score(&HELLO) + 0.625).abs() < 1e-12);
+            assert!((ExpectedRemaining.score(&CHJMX) + 0.25).abs() < 1e-12);
+        }
+
+        #[test]
+        fn gini_is_one_minus_the_sum_of_squares() {
+            assert!((Gini.score(&HELLO) - 0.375).abs() < 1e-12);
+            assert!((Gini.score(&CHJMX) - 0.75).abs() < 1e-12);
+        }
+
+        #[test]
+        fn estimated_guesses_count_the_winning_mask_as_done() {
+            let metric = EstimatedGuesses::new(4);
+            let won = PackedCorrectness::from([Correctness::Correct; 5]).as_index();
+            let lost = PackedCorrectness::from([Correctness::Wrong; 5]).as_index();
+
+            // "chjmx" can't win, and leaves one word whatever the mask.
+            let mut chjmx = [0.0; PackedCorrectness::COUNT];
+            for mask in (0..PackedCorrectness::COUNT)
+                .filter(|&mask| mask != won)
+                .take(4)
+            {
+                chjmx[mask] = 0.25;
+            }
+            assert!((metric.score(&chjmx) + 2.0).abs() < 1e-12);
+
+            // "hello" wins a quarter of the time, and leaves three words otherwise.
+            let mut hello = [0.0; PackedCorrectness::COUNT];
+            hello[won] = 0.25;
+            hello[lost] = 0.75;
+            let expected = 1.0 + 0.75 * (1.0 + f64::log2(3.0) / 4.6);
+            assert!((metric.score(&hello) + expected).abs() < 1e-12);
+        }
+
+        #[test]
+        fn worst_case_is_the_largest_bucket() {
+            assert_eq!(WorstCase.score(&HELLO), -0.75);
+            assert_eq!(WorstCase.score(&CHJMX), -0.25);
+        }
+    }
+}
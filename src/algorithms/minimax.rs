@@ -0,0 +1,109 @@
+use super::{fallback, partition};
+use crate::{Constraints, Correctness, DictionaryWithCounts, Guess, Guesser, Word};
+
+#[derive(Debug, Copy, Clone)]
+struct Candidate<'w> {
+    /// The word of this candidate.
+    word: &'w Word,
+
+    /// The count coming form the DictionaryWithCounts value parameter. This lets us know how
+    /// frequent this word is in the English language.
+    occurrence_count: f64,
+
+    /// The number of remaining words left in the worst case after guessing this candidate, i.e.
+    /// the size of the largest bucket of words that share a mask.
+    worst_case: f64,
+}
+
+/// Guesses the word that leaves the fewest remaining words in the worst case, rather than the one
+/// that gives the most information on average. This trades a slightly worse average for fewer
+/// catastrophic games.
+pub struct Minimax<'w> {
+    initial_remaining: DictionaryWithCounts<'w>,
+    remaining: DictionaryWithCounts<'w>,
+}
+
+impl<'w> Minimax<'w> {
+    /// Takes the words that could be the answer, which are also the words it guesses from.
+    pub fn new(remaining: DictionaryWithCounts<'w>) -> Self {
+        Self {
+            initial_remaining: remaining.clone(),
+            remaining,
+        }
+    }
+}
+
+impl<'w> Guesser<'w> for Minimax<'w> {
+    fn guess(&mut self, past_guesses: &[Guess<'w>]) -> &'w Word {
+        if let Some(last) = past_guesses.last() {
+            // Just like `Unoptimized`, earlier guesses were already filtered out on earlier turns.
+            let constraints = Constraints::from_guesses(std::slice::from_ref(last));
+            self.remaining.retain(|word, _| constraints.matches(word));
+        }
+        if self.remaining.is_empty() {
+            return fallback(self.initial_remaining.keys().copied());
+        }
+
+        let mut best: Option<Candidate<'w>> = None;
+
+        for (&word, &occurrence_count) in &self.remaining {
+            // Every word weighs one, so each bucket holds the number of words left after the
+            // mask it stands for.
+            let worst_case = partition(word, &self.remaining, Correctness::check_packed, |_| 1.0)
+                .into_iter()
+                .fold(0.0, f64::max);
+
+            // A new guess is better if no guess was previously made, or if the new guess leaves
+            // fewer words in the worst case, or leaves the same amount but is more common.
+            if best.is_none()
+                || worst_case < best.unwrap().worst_case
+                || (worst_case == best.unwrap().worst_case
+                    && occurrence_count > best.unwrap().occurrence_count)
+            {
+                best = Some(Candidate {
+                    word,
+                    occurrence_count,
+                    worst_case,
+                });
+            }
+        }
+
+        let best = best.expect("There is at least one remaining word");
+
+        best.word
+    }
+
+    fn name(&self) -> &'static str {
+        "minimax"
+    }
+
+    /// Goes back to the words that could be the answer before the first guess.
+    fn reset(&mut self) {
+        self.remaining.clone_from(&self.initial_remaining);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod play_wordle {
+        use crate::algorithms::Minimax;
+        use crate::{DictionaryWithCounts, Guesser, Word};
+
+        #[test]
+        fn minimax_picks_the_word_with_the_smallest_worst_case() {
+            // Any of the -ello words leaves the other three tied in one bucket, while "chjmx"
+            // tells all of them apart, even though it is far less common.
+            let words: [(&'static Word, f64); 5] = [
+                (b"hello", 100.0),
+                (b"jello", 90.0),
+                (b"cello", 80.0),
+                (b"mello", 70.0),
+                (b"chjmx", 1.0),
+            ];
+
+            let mut minimax = Minimax::new(DictionaryWithCounts::from_iter(words));
+
+            assert_eq!(minimax.guess(&[]), b"chjmx");
+        }
+    }
+}
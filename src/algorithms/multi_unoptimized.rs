@@ -0,0 +1,141 @@
+use super::metrics::{Entropy, ScoringMetric};
+use super::partition;
+use crate::{
+    Constraints, Correctness, Dictionary, DictionaryWithCounts, Guess, MultiGuesser, Word,
+    WORD_SIZE,
+};
+
+/// Plays `MultiWordle` the way `Unoptimized` plays a single board: every board keeps the words
+/// that may still be its answer, and the guess is the word of the dictionary with the most
+/// information summed over the boards that aren't solved yet. Once a board is down to one word,
+/// that word is guessed, since nothing else can solve the board.
+#[derive(Clone)]
+pub struct MultiUnoptimized<'l> {
+    dictionary: &'l Dictionary<'l>,
+    initial_remaining: DictionaryWithCounts<'l>,
+    boards: Vec<DictionaryWithCounts<'l>>,
+}
+
+impl<'l> MultiUnoptimized<'l> {
+    /// Takes a borrowed Dictionary that it uses to guess from, and the words that may be the
+    /// answer of every board alongside their counts.
+    pub fn new(dictionary: &'l Dictionary<'l>, remaining: DictionaryWithCounts<'l>) -> Self {
+        Self {
+            dictionary,
+            initial_remaining: remaining,
+            boards: Vec::new(),
+        }
+    }
+}
+
+impl<'l> MultiGuesser<'l> for MultiUnoptimized<'l> {
+    fn guess(&mut self, boards: &[Vec<Guess<'l>>]) -> &'l Word {
+        if self.boards.len() != boards.len() {
+            self.boards = vec![self.initial_remaining.clone(); boards.len()];
+        }
+
+        // Just like `Unoptimized`, earlier guesses were already filtered out on earlier turns.
+        for (remaining, past_guesses) in self.boards.iter_mut().zip(boards) {
+            if let Some(last) = past_guesses.last() {
+                let constraints = Constraints::from_guesses(std::slice::from_ref(last));
+                remaining.retain(|word, _| constraints.matches(word));
+            }
+        }
+
+        let unsolved = Vec::from_iter(
+            self.boards
+                .iter()
+                .zip(boards)
+                .filter(|(remaining, past_guesses)| {
+                    !remaining.is_empty()
+                        && past_guesses
+                            .last()
+                            .is_none_or(|last| last.mask != [Correctness::Correct; WORD_SIZE])
+                })
+                .map(|(remaining, _)| remaining),
+        );
+
+        if let Some(remaining) = unsolved.iter().find(|remaining| remaining.len() == 1) {
+            return remaining
+                .keys()
+                .next()
+                .expect("The board has one word left");
+        }
+
+        let reciprocals = Vec::from_iter(
+            unsolved
+                .iter()
+                .map(|remaining| remaining.values().sum::<f64>().recip()),
+        );
+
+        // Ties go to a word that could be the answer of some board, then to the word that comes
+        // first alphabetically, so the guess doesn't depend on the order the dictionary iterates
+        // in.
+        let mut best: Option<(&'l Word, (f64, bool))> = None;
+        for &word in self.dictionary {
+            let information = unsolved
+                .iter()
+                .zip(&reciprocals)
+                .map(|(remaining, &reciprocal)| {
+                    let buckets = partition(word, remaining, Correctness::check_packed, |count| {
+                        count * reciprocal
+                    });
+
+                    Entropy.score(&buckets)
+                })
+                .sum::<f64>();
+            let key = (
+                information,
+                unsolved
+                    .iter()
+                    .any(|remaining| remaining.contains_key(word)),
+            );
+
+            let better = best.is_none_or(|(best_word, best_key)| {
+                key.partial_cmp(&best_key)
+                    .is_some_and(|ordering| ordering.then(best_word.cmp(word)).is_gt())
+            });
+            if better {
+                best = Some((word, key));
+            }
+        }
+
+        let (word, _) = best.expect("Our guesser has to find at least one word");
+        word
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod play_wordle {
+        use crate::algorithms::MultiUnoptimized;
+        use crate::{MultiWordle, RepresentableAsWord, Word, Wordle};
+
+        const DICTIONARY: &str = include_str!("../../dictionary.txt");
+
+        #[test]
+        fn solves_every_board() {
+            let words = Vec::from_iter(
+                DICTIONARY
+                    .split_ascii_whitespace()
+                    .step_by(40)
+                    .map(|word_str| word_str.as_word()),
+            );
+            let wordle = Wordle::new(words.iter().copied());
+            let guesser = MultiUnoptimized::new(
+                wordle.get_dictionary(),
+                wordle.get_possible_answers().clone(),
+            );
+            let game = MultiWordle::new(wordle.clone());
+
+            let answers: [&Word; 2] = [words[10], words[200]];
+            let guesses = game.play_multi(&answers, guesser.clone());
+            assert!(guesses.is_some_and(|guesses| guesses >= 2));
+
+            // With the same answer on both boards, they're solved by the same guess.
+            let guesses = game.play_multi(&[words[10], words[10]], guesser.clone());
+            let single = game.play_multi(&[words[10]], guesser);
+            assert_eq!(guesses, single);
+        }
+    }
+}
@@ -0,0 +1,189 @@
+use super::{fallback, partition_words};
+use crate::{Constraints, Dictionary, DictionaryWithCounts, Guess, Guesser, Word};
+use std::collections::HashMap;
+
+/// A guesser that plays fair but searches the whole game tree: for the words that remain, it
+/// tries every word of the dictionary, then every word again for every mask that could come back,
+/// and so on until every answer is found, and picks the guess with the fewest expected guesses.
+/// No guesser that only sees the masks can do better on average, which makes it the bound to
+/// compare the heuristics against.
+///
+/// The search is exponential in the number of remaining words, so this is only usable on small
+/// sets. Results are remembered for every set of words the search comes across, so later guesses
+/// of a game, and games played by a clone, are mostly lookups.
+#[derive(Clone)]
+pub struct OptimalOffline<'w> {
+    guesses: Vec<&'w Word>,
+    initial_remaining: DictionaryWithCounts<'w>,
+    remaining: DictionaryWithCounts<'w>,
+    solved: HashMap<Vec<&'w Word>, (f64, &'w Word)>,
+}
+
+impl<'w> OptimalOffline<'w> {
+    /// Takes the Dictionary to guess from, and the words that may be the answer alongside
+    /// their counts.
+    pub fn new(dictionary: &Dictionary<'w>, remaining: DictionaryWithCounts<'w>) -> Self {
+        // Trying guesses in alphabetical order is what makes ties go to the first word.
+        let mut guesses = Vec::from_iter(dictionary.iter().copied());
+        guesses.sort_unstable();
+
+        Self {
+            guesses,
+            initial_remaining: remaining.clone(),
+            remaining,
+            solved: HashMap::new(),
+        }
+    }
+
+    /// The expected number of guesses, including the next one, that playing optimally from here
+    /// takes to find the answer.
+    pub fn expected_guesses(&mut self) -> f64 {
+        let words = self.sorted_remaining();
+        let (guesses, _) = self.solve(&words);
+
+        guesses
+    }
+
+    fn sorted_remaining(&self) -> Vec<(&'w Word, f64)> {
+        let mut words = Vec::from_iter(self.remaining.iter().map(|(&word, &count)| (word, count)));
+        words.sort_unstable_by_key(|&(word, _)| word);
+
+        words
+    }
+
+    /// The fewest expected guesses to find the answer among `words`, which have to be sorted, and
+    /// the guess to make next to get there.
+    fn solve(&mut self, words: &[(&'w Word, f64)]) -> (f64, &'w Word) {
+        if let [(word, _)] = words {
+            return (1.0, word);
+        }
+
+        let key = Vec::from_iter(words.iter().map(|&(word, _)| word));
+        if let Some(&solved) = self.solved.get(&key) {
+            return solved;
+        }
+
+        let total = words.iter().map(|&(_, count)| count).sum::<f64>();
+
+        // Ties go to the guess that could be the answer, then to the more common one, and then to
+        // the one that comes first alphabetically, which is the order `guesses` is in.
+        let mut best: Option<(&'w Word, (f64, bool, f64))> = None;
+        for i in 0..self.guesses.len() {
+            let guess = self.guesses[i];
+            let buckets = partition_words(guess, words);
+
+            // A guess that leaves every word in one bucket gets us nowhere, and would have the
+            // search go around in circles.
+            if buckets.len() == 1 {
+                continue;
+            }
+
+            let mut expected_guesses = 0.0;
+            for bucket in buckets.into_values() {
+                let weight = bucket.iter().map(|&(_, count)| count).sum::<f64>() / total;
+                let guesses = match bucket[..] {
+                    [(word, _)] if word == guess => 1.0,
+                    _ => 1.0 + self.solve(&bucket).0,
+                };
+
+                expected_guesses += weight * guesses;
+            }
+
+            let count = words
+                .binary_search_by_key(&guess, |&(word, _)| word)
+                .ok()
+                .map(|i| words[i].1);
+            let key = (-expected_guesses, count.is_some(), count.unwrap_or(0.0));
+            if best.is_none_or(|(_, best_key)| key > best_key) {
+                best = Some((guess, key));
+            }
+        }
+
+        let (guess, (expected_guesses, ..)) =
+            best.expect("Our guesser has to find at least one word");
+        let solved = (-expected_guesses, guess);
+        self.solved.insert(key, solved);
+
+        solved
+    }
+}
+
+impl<'w> Guesser<'w> for OptimalOffline<'w> {
+    fn guess(&mut self, past_guesses: &[Guess<'w>]) -> &'w Word {
+        if let Some(last) = past_guesses.last() {
+            // Just like `Unoptimized`, earlier guesses were already filtered out on earlier turns.
+            let constraints = Constraints::from_guesses(std::slice::from_ref(last));
+            self.remaining.retain(|word, _| constraints.matches(word));
+        }
+        if self.remaining.is_empty() {
+            return fallback(self.guesses.iter().copied());
+        }
+
+        let words = self.sorted_remaining();
+        let (_, guess) = self.solve(&words);
+
+        guess
+    }
+
+    fn name(&self) -> &'static str {
+        "optimal-offline"
+    }
+
+    /// Goes back to the words that could be the answer before the first guess. What the search
+    /// found out carries on from game to game, so the next game is mostly lookups.
+    fn reset(&mut self) {
+        self.remaining.clone_from(&self.initial_remaining);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod play_wordle {
+        use crate::algorithms::{OptimalOffline, Unoptimized};
+        use crate::{DictionaryWithCounts, Word, Wordle};
+
+        #[test]
+        fn optimal_offline_splits_when_no_answer_can() {
+            // Guessing any of the -ello words leaves the other three in one bucket, so "chjmx",
+            // which can't be the answer, is the only way to be sure of winning in two.
+            let answers: [&Word; 4] = [b"hello", b"jello", b"cello", b"mello"];
+            let wordle = Wordle::with_answers(
+                [b"chjmx"],
+                DictionaryWithCounts::from_iter(answers.map(|word| (word, 1.0))),
+            );
+            let mut guesser = OptimalOffline::new(
+                wordle.get_dictionary(),
+                wordle.get_possible_answers().clone(),
+            );
+
+            assert_eq!(guesser.expected_guesses(), 2.0);
+            for answer in answers {
+                assert_eq!(wordle.play(answer, guesser.clone()), Some(2));
+            }
+        }
+
+        #[test]
+        fn optimal_offline_is_never_worse_than_unoptimized() {
+            let answers: [&Word; 8] = [
+                b"hello", b"jello", b"cello", b"mello", b"world", b"moved", b"crane", b"slate",
+            ];
+            let wordle = Wordle::with_answers(
+                [b"chjmx", b"jumpy"],
+                DictionaryWithCounts::from_iter(answers.map(|word| (word, 1.0))),
+            );
+            let optimal = OptimalOffline::new(
+                wordle.get_dictionary(),
+                wordle.get_possible_answers().clone(),
+            );
+            let unoptimized = Unoptimized::new(
+                wordle.get_dictionary(),
+                wordle.get_possible_answers().clone(),
+            );
+
+            let optimal = wordle.evaluate(answers, || optimal.clone());
+            let unoptimized = wordle.evaluate(answers, || unoptimized.clone());
+            assert_eq!(optimal.losses, 0);
+            assert!(optimal.mean() <= unoptimized.mean());
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use super::fallback;
+use crate::{Constraints, Dictionary, Guess, Guesser, Word};
+
+/// A baseline guesser that picks a uniformly random word out of those that could still be the
+/// answer. It is seeded, so the same seed always plays the same game.
+pub struct RandomGuesser<'w> {
+    initial_remaining: Vec<&'w Word>,
+    remaining: Vec<&'w Word>,
+    state: u64,
+}
+
+impl<'w> RandomGuesser<'w> {
+    /// Takes a borrowed Dictionary that it uses to guess from, and a seed for its random numbers.
+    pub fn new(dictionary: &Dictionary<'w>, seed: u64) -> Self {
+        // A Dictionary iterates in a different order on every run, so we sort it to make sure the
+        // seed alone decides the game.
+        let mut remaining = Vec::from_iter(dictionary.iter().copied());
+        remaining.sort_unstable();
+
+        Self {
+            initial_remaining: remaining.clone(),
+            remaining,
+            // Xorshift gets stuck on a state of zero, so we nudge a zero seed away from it.
+            state: if seed == 0 {
+                0x9e37_79b9_7f4a_7c15
+            } else {
+                seed
+            },
+        }
+    }
+
+    /// Advances the xorshift64 generator, which is plenty for picking words and avoids pulling in
+    /// a dependency for random numbers.
+    fn next_random(&mut self) -> u64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        self.state
+    }
+}
+
+impl<'w> Guesser<'w> for RandomGuesser<'w> {
+    fn guess(&mut self, past_guesses: &[Guess<'w>]) -> &'w Word {
+        if let Some(last) = past_guesses.last() {
+            // Just like `Unoptimized`, earlier guesses were already filtered out on earlier turns.
+            let constraints = Constraints::from_guesses(std::slice::from_ref(last));
+            self.remaining.retain(|word| constraints.matches(word));
+        }
+        if self.remaining.is_empty() {
+            return fallback(self.initial_remaining.iter().copied());
+        }
+
+        let index = self.next_random() % self.remaining.len() as u64;
+        self.remaining[index as usize]
+    }
+
+    fn name(&self) -> &'static str {
+        "random"
+    }
+
+    /// Goes back to every word of the dictionary. The random numbers carry on from game to game,
+    /// so a reused guesser doesn't play the same game twice.
+    fn reset(&mut self) {
+        self.remaining.clone_from(&self.initial_remaining);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod play_wordle {
+        use crate::algorithms::RandomGuesser;
+        use crate::Wordle;
+
+        const DICTIONARY: &str = include_str!("../../dictionary.txt");
+
+        #[test]
+        fn same_seed_plays_the_same_game() {
+            let wordle = Wordle::from_str_list(DICTIONARY);
+
+            let first =
+                wordle.play_recorded(b"moved", RandomGuesser::new(wordle.get_dictionary(), 7));
+            let second =
+                wordle.play_recorded(b"moved", RandomGuesser::new(wordle.get_dictionary(), 7));
+
+            assert!(first.outcome.is_some());
+            assert_eq!(first.outcome, second.outcome);
+            assert!(first
+                .guesses
+                .iter()
+                .zip(&second.guesses)
+                .all(|(first, second)| first.word == second.word));
+        }
+
+        #[test]
+        fn zero_seed_still_plays() {
+            let wordle = Wordle::from_str_list(DICTIONARY);
+
+            assert!(wordle
+                .play(b"moved", RandomGuesser::new(wordle.get_dictionary(), 0))
+                .is_some());
+        }
+    }
+}
@@ -0,0 +1,275 @@
+use crate::{CorrectnessCache, DictionaryWithCounts, PackedCorrectness, Word};
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::Index;
+use std::sync::Arc;
+
+/// The words `Unoptimized` still considers possible answers, alongside their counts. It's either a
+/// map, or a bitset over the words it started with, where narrowing the words down clears their
+/// bits instead of taking them out of a map, see `Unoptimized::with_bitset`.
+#[derive(Clone)]
+pub(super) enum Remaining<'w> {
+    Map(DictionaryWithCounts<'w>),
+    Bits(WordBits<'w>),
+}
+
+/// Every word that could be the answer at the start, sorted, with its count at the same index,
+/// and one bit per word for whether it's still possible. The words and counts never change, so
+/// clones share them.
+#[derive(Clone)]
+pub(super) struct WordBits<'w> {
+    words: Arc<[&'w Word]>,
+    counts: Arc<[f64]>,
+    indices: Arc<HashMap<&'w Word, usize>>,
+    /// Where every word is among the answers of a `CorrectnessCache`, at the same index as the
+    /// word, if every word is one of them, see `Remaining::index_answers`.
+    answer_indices: Option<Arc<[usize]>>,
+    alive: Vec<u64>,
+    len: usize,
+}
+
+impl<'w> Remaining<'w> {
+    /// Stores `remaining` as a bitset over its words.
+    pub(super) fn bits(remaining: &DictionaryWithCounts<'w>) -> Self {
+        let mut words = Vec::from_iter(remaining.keys().copied());
+        words.sort_unstable();
+        let counts = Vec::from_iter(words.iter().map(|word| remaining[word]));
+        let indices = words
+            .iter()
+            .enumerate()
+            .map(|(i, &word)| (word, i))
+            .collect();
+
+        let mut alive = vec![0; words.len().div_ceil(64)];
+        for i in 0..words.len() {
+            alive[i / 64] |= 1 << (i % 64);
+        }
+
+        Self::Bits(WordBits {
+            len: words.len(),
+            words: words.into(),
+            counts: counts.into(),
+            indices: Arc::new(indices),
+            answer_indices: None,
+            alive,
+        })
+    }
+
+    /// Stores `remaining` the same way `self` is stored.
+    pub(super) fn like(&self, remaining: DictionaryWithCounts<'w>) -> Self {
+        match self {
+            Self::Map(_) => Self::Map(remaining),
+            Self::Bits(_) => Self::bits(&remaining),
+        }
+    }
+
+    pub(super) fn len(&self) -> usize {
+        match self {
+            Self::Map(map) => map.len(),
+            Self::Bits(bits) => bits.len,
+        }
+    }
+
+    pub(super) fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub(super) fn get(&self, word: &Word) -> Option<&f64> {
+        match self {
+            Self::Map(map) => map.get(word),
+            Self::Bits(bits) => bits
+                .indices
+                .get(word)
+                .filter(|&&i| bits.is_alive(i))
+                .map(|&i| &bits.counts[i]),
+        }
+    }
+
+    #[cfg(test)]
+    pub(super) fn contains_key(&self, word: &Word) -> bool {
+        self.get(word).is_some()
+    }
+
+    /// Every word and its count, in no particular order for a map, and alphabetically for a
+    /// bitset.
+    pub(super) fn iter(&self) -> Iter<'_, 'w> {
+        match self {
+            Self::Map(map) => Iter::Map(map.iter()),
+            Self::Bits(bits) => Iter::Bits {
+                bits,
+                block: 0,
+                remaining_in_block: bits.alive.first().copied().unwrap_or(0),
+            },
+        }
+    }
+
+    pub(super) fn keys(&self) -> impl Iterator<Item = &'w Word> + '_ {
+        self.iter().map(|(&word, _)| word)
+    }
+
+    pub(super) fn values(&self) -> impl Iterator<Item = &f64> + '_ {
+        self.iter().map(|(_, count)| count)
+    }
+
+    /// Keeps only the words `keep` returns true for.
+    pub(super) fn retain(&mut self, mut keep: impl FnMut(&'w Word) -> bool) {
+        match self {
+            Self::Map(map) => map.retain(|&word, _| keep(word)),
+            Self::Bits(bits) => {
+                for block in 0..bits.alive.len() {
+                    let mut remaining_in_block = bits.alive[block];
+                    while remaining_in_block != 0 {
+                        let bit = remaining_in_block.trailing_zeros() as usize;
+                        remaining_in_block &= remaining_in_block - 1;
+
+                        if !keep(bits.words[block * 64 + bit]) {
+                            bits.alive[block] &= !(1 << bit);
+                            bits.len -= 1;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    pub(super) fn to_map(&self) -> DictionaryWithCounts<'w> {
+        self.iter().map(|(&word, &count)| (word, count)).collect()
+    }
+
+    /// Looks up where the words of a bitset are among the answers of `cache`, for `masks` to look
+    /// their masks up by. A map isn't indexed, and neither is a bitset with a word that isn't one
+    /// of the answers of `cache`.
+    pub(super) fn index_answers(&mut self, cache: &CorrectnessCache) {
+        if let Self::Bits(bits) = self {
+            bits.answer_indices = bits
+                .words
+                .iter()
+                .map(|word| cache.answer_index(word))
+                .collect();
+        }
+    }
+
+    /// The mask `guess` gets against every remaining word, alongside the word's count, looked up
+    /// in `cache` by index rather than by word. This is only there for a bitset that was indexed
+    /// into `cache` by `index_answers`, and a `guess` that `cache` has.
+    pub(super) fn masks<'a>(
+        &'a self,
+        guess: &Word,
+        cache: &'a CorrectnessCache,
+    ) -> Option<impl Iterator<Item = (PackedCorrectness, f64)> + 'a> {
+        let Self::Bits(bits) = self else {
+            return None;
+        };
+        let answer_indices = bits.answer_indices.as_deref()?;
+        let guess_idx = cache.guess_index(guess)?;
+
+        Some(
+            bits.alive_indices()
+                .map(move |i| (cache.get(answer_indices[i], guess_idx), bits.counts[i])),
+        )
+    }
+
+    #[cfg(test)]
+    pub(super) fn is_indexed(&self) -> bool {
+        matches!(
+            self,
+            Self::Bits(WordBits {
+                answer_indices: Some(_),
+                ..
+            })
+        )
+    }
+}
+
+impl WordBits<'_> {
+    fn is_alive(&self, i: usize) -> bool {
+        self.alive[i / 64] & (1 << (i % 64)) != 0
+    }
+
+    /// The index of every word that is still possible, in order.
+    fn alive_indices(&self) -> impl Iterator<Item = usize> + '_ {
+        self.alive.iter().enumerate().flat_map(|(block, &alive)| {
+            std::iter::successors(Some(alive), |&rest| Some(rest & rest.wrapping_sub(1)))
+                .take_while(|&rest| rest != 0)
+                .map(move |rest| block * 64 + rest.trailing_zeros() as usize)
+        })
+    }
+}
+
+pub(super) enum Iter<'a, 'w> {
+    Map(std::collections::hash_map::Iter<'a, &'w Word, f64>),
+    Bits {
+        bits: &'a WordBits<'w>,
+        block: usize,
+        remaining_in_block: u64,
+    },
+}
+
+impl<'a, 'w> Iterator for Iter<'a, 'w> {
+    type Item = (&'a &'w Word, &'a f64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Self::Map(iter) => iter.next(),
+            Self::Bits {
+                bits,
+                block,
+                remaining_in_block,
+            } => {
+                while *remaining_in_block == 0 {
+                    *block += 1;
+                    *remaining_in_block = *bits.alive.get(*block)?;
+                }
+
+                let i = *block * 64 + remaining_in_block.trailing_zeros() as usize;
+                *remaining_in_block &= *remaining_in_block - 1;
+
+                Some((&bits.words[i], &bits.counts[i]))
+            }
+        }
+    }
+}
+
+impl Default for Remaining<'_> {
+    fn default() -> Self {
+        Self::Map(DictionaryWithCounts::new())
+    }
+}
+
+impl<'a, 'w> IntoIterator for &'a Remaining<'w> {
+    type Item = (&'a &'w Word, &'a f64);
+    type IntoIter = Iter<'a, 'w>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl Index<&Word> for Remaining<'_> {
+    type Output = f64;
+
+    fn index(&self, word: &Word) -> &f64 {
+        self.get(word)
+            .expect("The word should be one of the remaining words")
+    }
+}
+
+/// Two sets of remaining words are equal if they have the same words and counts, however they're
+/// stored.
+impl PartialEq for Remaining<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.to_map() == other.to_map()
+    }
+}
+
+impl<'w> PartialEq<DictionaryWithCounts<'w>> for Remaining<'w> {
+    fn eq(&self, other: &DictionaryWithCounts<'w>) -> bool {
+        self.to_map() == *other
+    }
+}
+
+impl fmt::Debug for Remaining<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
@@ -0,0 +1,177 @@
+use super::{fallback, partition_words, Unoptimized};
+use crate::{Constraints, Dictionary, DictionaryWithCounts, Guess, Guesser, Word};
+
+/// Plays like `Unoptimized` while many words remain, and switches to looking two guesses ahead
+/// once at most `threshold` remain, picking the guess with the fewest expected guesses left.
+///
+/// The lookahead tries every word of the dictionary as the next guess, and for every mask it could
+/// produce, every word that mask leaves as the guess after. Whatever is left after those two
+/// guesses is assumed to be guessed one word at a time, most common first. With `d` words in the
+/// dictionary and `r` remaining, that's `O(d⋅r²)` mask checks per guess, which is why it's only
+/// done once `r` is small.
+pub struct TwoPly<'l> {
+    dictionary: &'l Dictionary<'l>,
+    initial_remaining: DictionaryWithCounts<'l>,
+    remaining: DictionaryWithCounts<'l>,
+    threshold: usize,
+}
+
+impl<'l> TwoPly<'l> {
+    /// Takes a borrowed Dictionary to guess from, the words that may be the answer alongside their
+    /// counts, and how few of those have to remain before looking ahead.
+    pub fn new(
+        dictionary: &'l Dictionary<'l>,
+        remaining: DictionaryWithCounts<'l>,
+        threshold: usize,
+    ) -> Self {
+        Self {
+            dictionary,
+            initial_remaining: remaining.clone(),
+            remaining,
+            threshold,
+        }
+    }
+
+    /// The expected number of guesses to find the answer among `words` if `guess` is the next one.
+    fn expected_guesses(guess: &'l Word, words: &[(&'l Word, f64)]) -> f64 {
+        let total = words.iter().map(|&(_, count)| count).sum::<f64>();
+
+        partition_words(guess, words)
+            .into_values()
+            .map(|bucket| {
+                let weight = bucket.iter().map(|&(_, count)| count).sum::<f64>() / total;
+                let guesses = match bucket[..] {
+                    [(word, _)] if word == guess => 1.0,
+                    _ => 1.0 + Self::best_follow_up(&bucket),
+                };
+
+                weight * guesses
+            })
+            .sum()
+    }
+
+    /// The expected number of guesses to find the answer among `words` with the best next guess
+    /// among them, after which they're guessed one at a time.
+    fn best_follow_up(words: &[(&'l Word, f64)]) -> f64 {
+        if words.len() == 1 {
+            return 1.0;
+        }
+
+        let total = words.iter().map(|&(_, count)| count).sum::<f64>();
+        words
+            .iter()
+            .map(|&(guess, _)| {
+                partition_words(guess, words)
+                    .into_values()
+                    .map(|bucket| {
+                        let weight = bucket.iter().map(|&(_, count)| count).sum::<f64>() / total;
+                        let guesses = match bucket[..] {
+                            [(word, _)] if word == guess => 1.0,
+                            _ => 1.0 + one_at_a_time(bucket),
+                        };
+
+                        weight * guesses
+                    })
+                    .sum::<f64>()
+            })
+            .fold(f64::INFINITY, f64::min)
+    }
+}
+
+/// The expected number of guesses to find the answer among `words` by guessing them one at a
+/// time, most common first.
+fn one_at_a_time(mut words: Vec<(&Word, f64)>) -> f64 {
+    words.sort_unstable_by(|(a, a_count), (b, b_count)| b_count.total_cmp(a_count).then(a.cmp(b)));
+    let total = words.iter().map(|&(_, count)| count).sum::<f64>();
+
+    words
+        .iter()
+        .enumerate()
+        .map(|(i, &(_, count))| (i + 1) as f64 * count / total)
+        .sum()
+}
+
+impl<'l> Guesser<'l> for TwoPly<'l> {
+    fn guess(&mut self, past_guesses: &[Guess<'l>]) -> &'l Word {
+        if let Some(last) = past_guesses.last() {
+            // Just like `Unoptimized`, earlier guesses were already filtered out on earlier turns.
+            let constraints = Constraints::from_guesses(std::slice::from_ref(last));
+            self.remaining.retain(|word, _| constraints.matches(word));
+        }
+        if self.remaining.is_empty() {
+            return fallback(self.dictionary.iter().copied());
+        }
+
+        if self.remaining.len() > self.threshold {
+            return Unoptimized::new(self.dictionary, self.remaining.clone()).guess(&[]);
+        }
+
+        let words = Vec::from_iter(self.remaining.iter().map(|(&word, &count)| (word, count)));
+        let mut guesses = Vec::from_iter(self.dictionary.iter().copied());
+        guesses.sort_unstable();
+
+        // Ties go to the guess that could be the answer, then to the more common one, and then to
+        // the one that comes first alphabetically, which is the order `guesses` is in.
+        let mut best: Option<(&'l Word, (f64, bool, f64))> = None;
+        for guess in guesses {
+            let count = self.remaining.get(guess).copied();
+            let key = (
+                -Self::expected_guesses(guess, &words),
+                count.is_some(),
+                count.unwrap_or(0.0),
+            );
+
+            if best.is_none_or(|(_, best_key)| key > best_key) {
+                best = Some((guess, key));
+            }
+        }
+
+        let (best, _) = best.expect("Our guesser has to find at least one word");
+
+        best
+    }
+
+    fn name(&self) -> &'static str {
+        "two-ply"
+    }
+
+    /// Goes back to the words that could be the answer before the first guess.
+    fn reset(&mut self) {
+        self.remaining.clone_from(&self.initial_remaining);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod play_wordle {
+        use crate::algorithms::{TwoPly, Unoptimized};
+        use crate::{DictionaryWithCounts, Guesser, Word, Wordle};
+
+        #[test]
+        fn two_ply_guesses_the_likely_answer_over_the_best_split() {
+            // "jumpy" tells all three -ello words apart, so it has the most information, but it
+            // always takes two guesses. Guessing "hello" is right 60% of the time and otherwise
+            // leaves a coin flip between the other two, for 0.6⋅1 + 0.4⋅2.5 = 1.6 guesses, which
+            // is the best any guess can do.
+            let words: [(&'static Word, f64); 3] =
+                [(b"hello", 6.0), (b"jello", 2.0), (b"mello", 2.0)];
+            let wordle = Wordle::with_answers([b"jumpy"], DictionaryWithCounts::from(words));
+
+            let mut unoptimized = Unoptimized::new(wordle.get_dictionary(), words.into());
+            assert_eq!(unoptimized.guess(&[]), b"jumpy");
+
+            let mut two_ply = TwoPly::new(wordle.get_dictionary(), words.into(), 20);
+            assert_eq!(two_ply.guess(&[]), b"hello");
+        }
+
+        #[test]
+        fn two_ply_delegates_above_the_threshold() {
+            let words: [(&'static Word, f64); 3] =
+                [(b"hello", 6.0), (b"jello", 2.0), (b"mello", 2.0)];
+            let wordle = Wordle::with_answers([b"jumpy"], DictionaryWithCounts::from(words));
+
+            let mut two_ply = TwoPly::new(wordle.get_dictionary(), words.into(), 2);
+            assert_eq!(two_ply.guess(&[]), b"jumpy");
+        }
+    }
+}
@@ -1,157 +1,1723 @@
-use roget::{Correctness, Dictionary, DictionaryWithCounts, Guess, Guesser, Word, WORD_SIZE};
+use super::metrics::{Entropy, ScoringMetric};
+use super::remaining::Remaining;
+use super::{fallback, partition_into};
+use crate::{
+    Correctness, CorrectnessCache, Dictionary, DictionaryWithCounts, Guess, Guesser,
+    PackedCorrectness, Word, Wordle, WORD_SIZE,
+};
+use std::cmp::Ordering;
 use std::collections::HashMap;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
 
+/// A word that `Unoptimized` considered guessing, and how it scored.
 #[derive(Debug, Copy, Clone)]
-struct Candidate {
+pub struct Candidate<'w> {
     /// The word of this candidate.
-    word: &'static Word,
+    pub word: &'w Word,
 
     /// The count coming form the DictionaryWithCounts value parameter. This lets us know how
-    /// frequent this word is in the English language.
-    occurrence_count: f64,
+    /// frequent this word is in the English language. This is 0 for words that can't be the
+    /// answer anymore.
+    pub occurrence_count: f64,
+
+    /// Whether this candidate is still among the remaining possible answers.
+    pub possible_answer: bool,
 
     /// How much this candidate will reduce the space of possible states.
     /// Information of 2 bits means that the candidate will cut the remaining space to one fourth
-    /// of it's current size.
-    expected_information: f64,
+    /// of it's current size. With another metric than `Entropy`, this is that metric's score.
+    pub expected_information: f64,
+
+    /// The probability that this candidate is the answer, according to the answer prior. This is
+    /// only computed when `Unoptimized::with_weight` is used, and is 0 otherwise.
+    pub answer_probability: f64,
+}
+
+impl Candidate<'_> {
+    /// What we rank candidates by: the expected information, plus the weighted probability that
+    /// this candidate is the answer.
+    fn score(&self, weight: f64) -> f64 {
+        self.expected_information + weight * self.answer_probability
+    }
+}
+
+/// Frequency ranks around this one are where words go from plausible to implausible answers in
+/// the prior used by `Unoptimized::with_weight`.
+const ANSWER_PRIOR_MIDPOINT_RANK: f64 = 3000.0;
+
+/// How many ranks it takes the answer prior to go most of the way from plausible to implausible.
+const ANSWER_PRIOR_WIDTH: f64 = 300.0;
+
+/// About how many bits of information a guess gives on average over a game, which is what
+/// `Unoptimized::estimated_guesses_remaining` expects every guess to take off.
+const AVERAGE_BITS_PER_GUESS: f64 = 2.25;
+
+/// How `Unoptimized` turns the occurrence counts of the remaining words into how likely each is
+/// to be the answer, which weighs the masks when scoring a guess, and breaks ties between
+/// guesses by how common they are.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Prior {
+    /// Uses the counts as they are, which is the default. Counts span many orders of magnitude, so
+    /// a few very common words outweigh everything else.
+    Raw,
+    /// Makes every remaining word just as likely.
+    Uniform,
+    /// Ranks words by their count, most common first, and puts the rank through a sigmoid that is
+    /// halfway down at `midpoint`, and falls off faster the higher `steepness` is. This is the
+    /// prior 3Blue1Brown uses, where the few thousand most common words are all plausible answers
+    /// and the rest are not.
+    SigmoidRank { steepness: f64, midpoint: f64 },
+}
+
+impl Prior {
+    /// The weight this prior gives every word of `counts`.
+    fn apply<'w>(self, counts: &DictionaryWithCounts<'w>) -> DictionaryWithCounts<'w> {
+        match self {
+            Self::Raw => counts.clone(),
+            Self::Uniform => counts.keys().map(|&word| (word, 1.0)).collect(),
+            Self::SigmoidRank {
+                steepness,
+                midpoint,
+            } => {
+                // Words that are just as common are ranked alphabetically, so that the prior
+                // doesn't depend on the order the map iterates in.
+                let mut by_count = Vec::from_iter(counts.iter());
+                by_count.sort_unstable_by(|(a, a_count), (b, b_count)| {
+                    b_count.total_cmp(a_count).then(a.cmp(b))
+                });
+
+                by_count
+                    .into_iter()
+                    .enumerate()
+                    .map(|(rank, (&word, _))| {
+                        let x = (rank as f64 - midpoint) * steepness;
+                        (word, 1.0 / (1.0 + x.exp()))
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+/// Remembers the best candidate for every set of remaining words that `Unoptimized` guessed from,
+/// so that games reaching the same state, like every game at its opener, only score the
+/// dictionary once between them. It can be shared between threads.
+///
+/// Entries are keyed by every remaining word and its count, and by whether the guess is the
+/// opening one, which `Unoptimized::with_opener_pool` picks from a pool of its own. That is all a
+/// guess depends on besides how the guesser was set up, so a cache belongs to the setup of the
+/// first guesser that uses it: its dictionary, metric, weight and answer prior, opener pool and
+/// `with_prefer_candidates_when_few` threshold. Metrics are told apart by their type. A guesser
+/// set up any differently panics when it goes to use the cache, rather than being handed guesses
+/// that were best for another setup.
+#[derive(Debug, Default)]
+pub struct EntropyCache<'w> {
+    best: Mutex<HashMap<CachedState<'w>, Candidate<'w>>>,
+    config: OnceLock<CacheConfig>,
+}
+
+/// Whether the guess is the opening one, and the remaining words with the bits of their counts,
+/// sorted so that the same words make the same key.
+type CachedState<'w> = (bool, Vec<(&'w Word, u64)>);
+
+/// Everything besides the remaining words that decides which candidate `Unoptimized` picks, see
+/// `EntropyCache`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+struct CacheConfig {
+    /// The address of the dictionary. It is borrowed for as long as the cache is, so no other
+    /// dictionary can turn up at the same address while the cache is in use.
+    dictionary: usize,
+    metric: &'static str,
+    weight: u64,
+    answer_priors: u64,
+    /// The address and length of the opener pool.
+    opener_pool: Option<(usize, usize)>,
+    prefer_candidates_when_few: usize,
+}
+
+impl<'w> EntropyCache<'w> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// How many states have their best candidate remembered.
+    pub fn len(&self) -> usize {
+        self.entries().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Ties the cache to `config` if it isn't tied to a setup yet.
+    ///
+    /// Panics if it's tied to another one.
+    fn claim(&self, config: CacheConfig) {
+        let claimed = self.config.get_or_init(|| config);
+        assert!(
+            *claimed == config,
+            "An EntropyCache can only be shared by guessers set up alike, but it was filled with \
+             {:?} and is used with {:?}",
+            claimed,
+            config
+        );
+    }
+
+    fn entries(&self) -> std::sync::MutexGuard<'_, HashMap<CachedState<'w>, Candidate<'w>>> {
+        // A thread that panicked while holding the lock can't have left a half-written entry
+        // behind, so the map is still good to use.
+        self.best
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
 }
 
-pub struct Unoptimized<'l> {
-    dictionary: &'l Dictionary,
-    remaining: DictionaryWithCounts,
+/// Cloning an `Unoptimized` copies the words it has left, so a clone made mid-game carries on
+/// from where the original was without affecting it.
+///
+/// Guesses are scored by `M`, which is Shannon entropy unless another metric is picked through
+/// `with_metric`.
+#[derive(Clone)]
+pub struct Unoptimized<'l, M: ScoringMetric = Entropy> {
+    dictionary: &'l Dictionary<'l>,
+    metric: M,
+    remaining: Remaining<'l>,
+    /// What `remaining` was before the first guess, for `reset` to go back to.
+    initial_remaining: Remaining<'l>,
+    /// The counts the guesser was built with, before any prior, which `with_prior` and
+    /// `with_weight` go by, so that neither depends on what the other did before it.
+    counts: DictionaryWithCounts<'l>,
+    cache: Option<&'l CorrectnessCache<'l>>,
+    entropy_cache: Option<&'l EntropyCache<'l>>,
+    weight: f64,
+    answer_priors: DictionaryWithCounts<'l>,
+    /// A hash of `answer_priors`, for telling setups apart, see `EntropyCache`.
+    answer_priors_fingerprint: u64,
+    opener: Option<&'l Word>,
+    opener_pool: Option<&'l [&'l Word]>,
+    prefer_candidates_when_few: usize,
+    /// The score of the word `guess` picked last this game, if it picked one by its score.
+    last_score: Option<f64>,
+    /// Whether `with_history` already narrowed the remaining words down, in which case the next
+    /// guess isn't the opening one, even when `guess` is given no past guesses.
+    history_applied: bool,
+    /// How long each guess took, if profiling.
+    timings: Option<Vec<Duration>>,
+    /// Reused by every candidate to partition the remaining words into, so scoring a candidate
+    /// doesn't need a fresh buffer. Only the serial search uses this.
+    #[cfg(any(test, not(feature = "rayon")))]
+    scratch: Vec<f64>,
+    /// How many times `entropy` ran, shared between clones, so tests can tell when it's skipped.
+    #[cfg(test)]
+    entropy_calls: std::sync::Arc<std::sync::atomic::AtomicUsize>,
 }
 
 impl<'l> Unoptimized<'l> {
-    /// Takes a borrowed Dictionary that it uses to guess from.
-    pub fn new(dictionary: &'l Dictionary, remaining: DictionaryWithCounts) -> Self {
+    /// Takes a borrowed Dictionary that it uses to guess from, and the words that may be the
+    /// answer alongside their counts. Guesses are scored by how well they split up `remaining`,
+    /// so a word that can't be the answer can still be guessed to narrow the answers down.
+    pub fn new(dictionary: &'l Dictionary<'l>, remaining: DictionaryWithCounts<'l>) -> Self {
+        Self::new_with_prior(dictionary, remaining, Prior::Raw)
+    }
+
+    /// Like `new`, but weighs the words that may be the answer by what `prior` makes of their
+    /// counts instead of by the counts themselves, see `with_prior`.
+    pub fn new_with_prior(
+        dictionary: &'l Dictionary<'l>,
+        counts: DictionaryWithCounts<'l>,
+        prior: Prior,
+    ) -> Self {
+        let remaining = prior.apply(&counts);
+
         Self {
             dictionary,
-            remaining,
+            metric: Entropy,
+            initial_remaining: Remaining::Map(remaining.clone()),
+            remaining: Remaining::Map(remaining),
+            counts,
+            cache: None,
+            entropy_cache: None,
+            weight: 0.0,
+            answer_priors: DictionaryWithCounts::new(),
+            answer_priors_fingerprint: 0,
+            opener: None,
+            opener_pool: None,
+            prefer_candidates_when_few: 0,
+            last_score: None,
+            history_applied: false,
+            timings: None,
+            #[cfg(any(test, not(feature = "rayon")))]
+            scratch: vec![0.0; PackedCorrectness::COUNT],
+            #[cfg(test)]
+            entropy_calls: Default::default(),
         }
     }
+
+    /// Guesses from the dictionary of `wordle`, with its possible answers weighed by the counts it
+    /// keeps, see `Wordle::counted_answers`. This is `new` for a game that holds its own counts.
+    pub fn from_wordle(wordle: &'l Wordle<'l>) -> Self {
+        Self::new(wordle.get_dictionary(), wordle.counted_answers())
+    }
 }
 
-impl<'l> Guesser for Unoptimized<'l> {
-    /// Applying information theory, we try to guess the word. Guessing is a two-step procedure:
-    /// First, we try to limit our space of remaining words to only those that could be possible
-    /// given the last mask. Then, we loop over the remaining words to figure out which provides
-    /// the largest information, and return that.
-    fn guess(&mut self, past_guesses: &[Guess]) -> &'static Word {
+impl<'l, M: ScoringMetric> Unoptimized<'l, M> {
+    /// Scores guesses by `metric` instead of by the metric used so far, keeping everything else.
+    pub fn with_metric<N: ScoringMetric>(self, metric: N) -> Unoptimized<'l, N> {
+        Unoptimized {
+            dictionary: self.dictionary,
+            metric,
+            remaining: self.remaining,
+            initial_remaining: self.initial_remaining,
+            counts: self.counts,
+            cache: self.cache,
+            entropy_cache: self.entropy_cache,
+            weight: self.weight,
+            answer_priors: self.answer_priors,
+            answer_priors_fingerprint: self.answer_priors_fingerprint,
+            opener: self.opener,
+            opener_pool: self.opener_pool,
+            prefer_candidates_when_few: self.prefer_candidates_when_few,
+            last_score: self.last_score,
+            history_applied: self.history_applied,
+            timings: self.timings,
+            #[cfg(any(test, not(feature = "rayon")))]
+            scratch: self.scratch,
+            #[cfg(test)]
+            entropy_calls: self.entropy_calls,
+        }
+    }
+
+    /// Guesses `opener` first instead of computing the best first guess. The first guess only
+    /// depends on the dictionary and the counts, never on the answer, so when playing many games
+    /// it can be computed once up front and handed to every game through this.
+    pub fn with_opener(mut self, opener: &'l Word) -> Self {
+        self.opener = Some(opener);
+        self
+    }
+
+    /// Narrows the remaining words down by every guess of `past_guesses` up front, for taking over
+    /// a game that was started elsewhere, like by hand. `guess` only narrows them down by the last
+    /// of the guesses it's given, since it expects to have seen the ones before, so without this
+    /// the guesses of the game up to here would be lost. `guess` can then be given the same
+    /// guesses, or none at all, and neither `with_opener` nor `with_opener_pool` will take the
+    /// next guess for the first one. `reset` still goes back to before these guesses.
+    pub fn with_history(mut self, past_guesses: &[Guess<'l>]) -> Self {
+        for guess in past_guesses {
+            self.narrow(std::slice::from_ref(guess));
+        }
+        self.history_applied |= !past_guesses.is_empty();
+        self
+    }
+
+    /// Only scores the words of `pool` for the first guess, instead of the whole dictionary,
+    /// which is the slowest guess to compute. The best openers are well known, so a pool of a few
+    /// dozen of them loses little, if anything, over scanning every word. Later guesses are
+    /// scored from the whole dictionary as usual. An opener set by `with_opener` wins over this.
+    pub fn with_opener_pool(mut self, pool: &'l [&'l Word]) -> Self {
+        self.opener_pool = Some(pool);
+        self
+    }
+
+    /// Scores candidates by `expected_information + weight⋅p(candidate is the answer)` instead of
+    /// by expected information alone, which is what a weight of 0 (the default) does.
+    ///
+    /// Following 3Blue1Brown, `p` does not use raw occurrence counts, which span many orders of
+    /// magnitude. Instead, words are ranked by their count and put through a sigmoid over that
+    /// rank, so that the few thousand most common words are all plausible answers, and the rest
+    /// are not.
+    ///
+    /// At the opener, `p` is roughly 1/3000 for every plausible word, so the weight mostly
+    /// matters late in the game, when few candidates remain. With the counts from `joined.txt`,
+    /// the opener stays "tares" for weights up to 8, and only moves to the more common "tears"
+    /// at weights of around 300. The "soare" and "crane" openers from 3Blue1Brown's video can't
+    /// be reproduced with a weight alone: they come from taking the entropy uniformly over the
+    /// list of possible answers, whereas we weigh every word by its occurrence count.
+    pub fn with_weight(mut self, weight: f64) -> Self {
+        self.answer_priors = Prior::SigmoidRank {
+            steepness: ANSWER_PRIOR_WIDTH.recip(),
+            midpoint: ANSWER_PRIOR_MIDPOINT_RANK,
+        }
+        .apply(&self.counts);
+        self.answer_priors_fingerprint = fingerprint(&self.answer_priors);
+        self.weight = weight;
+        self
+    }
+
+    /// Replaces the counts of the remaining words by the weights `prior` gives them, which is
+    /// what the masks are weighed by and ties are broken by from then on. `Prior::Raw`, the
+    /// default, keeps them as they are.
+    ///
+    /// The weights always come from the counts the guesser was built with, over every word that
+    /// could be the answer at the start, so only the last prior counts, and the answer prior of
+    /// `with_weight` is the same whatever prior was picked.
+    pub fn with_prior(mut self, prior: Prior) -> Self {
+        let weights = prior.apply(&self.counts);
+
+        self.remaining = self.remaining.like(
+            self.remaining
+                .keys()
+                .map(|word| (word, weights[word]))
+                .collect(),
+        );
+        self.initial_remaining = self.initial_remaining.like(weights);
+        self.index_answers()
+    }
+
+    /// Keeps the remaining words as a bitset over the words that could be the answer at the
+    /// start, with their counts alongside, instead of as a map. Narrowing the words down then
+    /// clears bits instead of taking words out of a map, and resetting copies the bits instead of
+    /// the map, which adds up over thousands of games. The guesses are the same either way.
+    pub fn with_bitset(mut self) -> Self {
+        self.remaining = Remaining::bits(&self.remaining.to_map());
+        self.initial_remaining = Remaining::bits(&self.initial_remaining.to_map());
+        self.index_answers()
+    }
+
+    /// Once at most `threshold` words could be the answer, only guesses those words, instead of
+    /// words that would tell them apart better but can't be the answer themselves. Near the end
+    /// of a game, a guess that might win can be worth more than one that's sure to narrow things
+    /// down. The default of 0 always guesses from the whole dictionary.
+    pub fn with_prefer_candidates_when_few(mut self, threshold: usize) -> Self {
+        self.prefer_candidates_when_few = threshold;
+        self
+    }
+
+    /// Records how long every call to `guess` takes, for `timings` to return. This is off by
+    /// default, and reads the clock only when on, so it's safe to leave off on targets without a
+    /// clock, like wasm32.
+    pub fn with_profiling(mut self) -> Self {
+        self.timings = Some(Vec::new());
+        self
+    }
+
+    /// How long each guess took so far, oldest first, or nothing if not profiling.
+    pub fn timings(&self) -> &[Duration] {
+        self.timings.as_deref().unwrap_or_default()
+    }
+
+    /// Looks masks up in `cache` instead of computing them, wherever the cache has them. With
+    /// `with_bitset`, and every word that could be the answer among the answers of `cache`,
+    /// scoring looks them up by the indices of the words rather than by the words.
+    pub fn with_cache(mut self, cache: &'l CorrectnessCache<'l>) -> Self {
+        self.cache = Some(cache);
+        self.index_answers()
+    }
+
+    /// Indexes the bitset of the remaining words into the cache, if there are both, see
+    /// `Remaining::index_answers`.
+    fn index_answers(mut self) -> Self {
+        if let Some(cache) = self.cache {
+            self.remaining.index_answers(cache);
+            self.initial_remaining.index_answers(cache);
+        }
+        self
+    }
+
+    /// Remembers the best candidate for every state in `cache`, and looks states up there before
+    /// scoring the dictionary for them.
+    ///
+    /// Guessing panics if `cache` was already used by a guesser set up differently, see
+    /// `EntropyCache`. Options can still be set after this, since the setup is only checked once
+    /// the cache is used.
+    pub fn with_entropy_cache(mut self, cache: &'l EntropyCache<'l>) -> Self {
+        self.entropy_cache = Some(cache);
+        self
+    }
+
+    /// What the cache of `with_entropy_cache` has to be tied to for this guesser.
+    fn cache_config(&self) -> CacheConfig {
+        CacheConfig {
+            dictionary: std::ptr::from_ref(self.dictionary) as usize,
+            metric: std::any::type_name::<M>(),
+            weight: self.weight.to_bits(),
+            answer_priors: self.answer_priors_fingerprint,
+            opener_pool: self
+                .opener_pool
+                .map(|pool| (pool.as_ptr() as usize, pool.len())),
+            prefer_candidates_when_few: self.prefer_candidates_when_few,
+        }
+    }
+
+    fn check(&self, answer: &Word, guessed_word: &Word) -> PackedCorrectness {
+        self.cache
+            .and_then(|cache| cache.get_by_word(answer, guessed_word))
+            .unwrap_or_else(|| Correctness::check_packed(answer, guessed_word))
+    }
+
+    /// The words worth scoring as the next guess: the whole dictionary, the opener pool for the
+    /// `opening` guess if there is one, see `with_opener_pool`, or only the remaining words once
+    /// there are few enough of them, see `with_prefer_candidates_when_few`.
+    fn pool(&self, opening: bool) -> Vec<&'l Word> {
+        if let (Some(opener_pool), true) = (self.opener_pool, opening) {
+            Vec::from_iter(opener_pool.iter().copied())
+        } else if !self.remaining.is_empty()
+            && self.remaining.len() <= self.prefer_candidates_when_few
+        {
+            Vec::from_iter(self.remaining.keys())
+        } else {
+            Vec::from_iter(self.dictionary.iter().copied())
+        }
+    }
+
+    /// Finds the best candidate among the remaining words, or `None` if no words remain. With the
+    /// `rayon` feature, candidates are scored on every available core.
+    fn best_candidate(&mut self, opening: bool) -> Option<Candidate<'l>> {
+        #[cfg(feature = "rayon")]
+        return self.best_candidate_parallel(opening);
+
+        #[cfg(not(feature = "rayon"))]
+        return self.best_candidate_serial(opening);
+    }
+
+    #[cfg(any(test, not(feature = "rayon")))]
+    fn best_candidate_serial(&mut self, opening: bool) -> Option<Candidate<'l>> {
+        let totals = self.totals();
+        let mut scratch = std::mem::take(&mut self.scratch);
+
+        // We loop over every allowed guess, not only the possible answers:
+        let best = self
+            .pool(opening)
+            .into_iter()
+            .map(|word| self.candidate(word, totals, &mut scratch))
+            .reduce(|best, candidate| self.better(best, candidate));
+
+        self.scratch = scratch;
+        best
+    }
+
+    /// Scores the allowed guesses on rayon's thread pool. Since `better` is a total order, this
+    /// picks the exact same candidate as `best_candidate_serial` regardless of how rayon splits
+    /// the words up. Each of rayon's jobs gets its own scratch buffer, since they can't share the
+    /// one in `self`.
+    ///
+    /// Nothing here starts threads of its own, so guesses made from rayon's pool, like the games
+    /// `main` plays, share its threads instead of each starting a thread per core.
+    #[cfg(feature = "rayon")]
+    fn best_candidate_parallel(&self, opening: bool) -> Option<Candidate<'l>> {
+        use rayon::prelude::*;
+
+        let totals = self.totals();
+
+        self.pool(opening)
+            .into_par_iter()
+            .map_init(
+                || vec![0.0; PackedCorrectness::COUNT],
+                |scratch, word| self.candidate(word, totals, scratch),
+            )
+            .reduce_with(|best, candidate| self.better(best, candidate))
+    }
+
+    /// One over the total occurrence count, and the total answer prior of the remaining words,
+    /// which turn counts and priors into probabilities. The reciprocal is what the hot loop in
+    /// `entropy` multiplies every count by, which is cheaper than dividing by the total.
+    fn totals(&self) -> (f64, f64) {
+        let occurrence_reciprocal = self.remaining.values().sum::<f64>().recip();
+        let total_answer_prior = self
+            .remaining
+            .keys()
+            .filter_map(|word| self.answer_priors.get(word))
+            .sum::<f64>();
+
+        (occurrence_reciprocal, total_answer_prior)
+    }
+
+    /// How many bits of information guessing `word` gives on average, given the words that are
+    /// still possible answers. Each bit halves the number of possible answers, so a word with 3
+    /// bits is expected to leave an eighth of them. With another metric than `Entropy`, this is
+    /// the score of that metric instead.
+    pub fn expected_information(&self, word: &Word) -> f64 {
+        let (occurrence_reciprocal, _) = self.totals();
+        let mut scratch = [0.0; PackedCorrectness::COUNT];
+
+        self.entropy(word, occurrence_reciprocal, &mut scratch)
+    }
+
+    fn entropy(&self, word: &Word, occurrence_reciprocal: f64, scratch: &mut [f64]) -> f64 {
+        #[cfg(test)]
+        self.entropy_calls
+            .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+
+        // We need to find all the masks that can result from using this word, and calculate
+        // the probability of each as the amount of words in the remaining dictionary that
+        // satisfy this mask. The metric turns that distribution into a measure of the quality of
+        // the word.
+        let weight = |future_occurrence_count| future_occurrence_count * occurrence_reciprocal;
+        match self
+            .cache
+            .and_then(|cache| self.remaining.masks(word, cache))
+        {
+            Some(masks) => {
+                scratch.fill(0.0);
+                for (mask, future_occurrence_count) in masks {
+                    scratch[mask.as_index()] += weight(future_occurrence_count);
+                }
+            }
+            None => partition_into(
+                word,
+                &self.remaining,
+                |answer, guessed_word| self.check(answer, guessed_word),
+                weight,
+                scratch,
+            ),
+        }
+
+        self.metric.score(scratch)
+    }
+
+    fn candidate(
+        &self,
+        word: &'l Word,
+        (occurrence_reciprocal, total_answer_prior): (f64, f64),
+        scratch: &mut [f64],
+    ) -> Candidate<'l> {
+        let entropy = self.entropy(word, occurrence_reciprocal, scratch);
+
+        self.scored(word, total_answer_prior, entropy)
+    }
+
+    /// A candidate for `word`, given its expected information.
+    fn scored(
+        &self,
+        word: &'l Word,
+        total_answer_prior: f64,
+        expected_information: f64,
+    ) -> Candidate<'l> {
+        // Words that were ruled out keep their prior, so only the remaining words get a chance of
+        // being the answer.
+        let occurrence_count = self.remaining.get(word).copied();
+        let answer_probability = match (occurrence_count, self.answer_priors.get(word)) {
+            (Some(_), Some(answer_prior)) => answer_prior / total_answer_prior,
+            _ => 0.0,
+        };
+
+        Candidate {
+            word,
+            occurrence_count: occurrence_count.unwrap_or(0.0),
+            possible_answer: occurrence_count.is_some(),
+            expected_information,
+            answer_probability,
+        }
+    }
+
+    /// A new guess is better if it has a higher score, or has the same exact score but could be
+    /// the answer, or is more common, or is just as common but comes first alphabetically. That
+    /// last comparison makes this a total order, so the guess doesn't depend on the order the
+    /// dictionary iterates in.
+    fn better(&self, best: Candidate<'l>, candidate: Candidate<'l>) -> Candidate<'l> {
+        if self.compare(&candidate, &best) == Ordering::Greater {
+            candidate
+        } else {
+            best
+        }
+    }
+
+    /// The order behind `better`, where the better candidate is the greater one.
+    fn compare(&self, a: &Candidate, b: &Candidate) -> Ordering {
+        let key = |candidate: &Candidate| {
+            (
+                candidate.score(self.weight),
+                candidate.possible_answer,
+                candidate.occurrence_count,
+            )
+        };
+
+        key(a)
+            .partial_cmp(&key(b))
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| b.word.cmp(a.word))
+    }
+
+    /// Every word `guess` would consider as a candidate, which is usually the whole dictionary,
+    /// from the one `guess` would pick down to the worst. Like `guess`, this first narrows the
+    /// remaining words down by the last of `past_guesses`, which it's fine to do again when `guess`
+    /// is then called with the same guesses.
+    pub fn ranked_guesses(&mut self, past_guesses: &[Guess<'l>]) -> Vec<Candidate<'l>> {
+        self.narrow(past_guesses);
+
+        let totals = self.totals();
+        let mut scratch = [0.0; PackedCorrectness::COUNT];
+        let mut candidates = Vec::from_iter(
+            self.pool(self.opening(past_guesses))
+                .into_iter()
+                .map(|word| self.candidate(word, totals, &mut scratch)),
+        );
+        candidates.sort_unstable_by(|a, b| self.compare(b, a));
+
+        candidates
+    }
+
+    /// The `k` best guesses and their expected information, best first, for showing a few options
+    /// instead of only the one `guess` picks. This narrows the remaining words down like
+    /// `ranked_guesses` does.
+    ///
+    /// The best guesses are often variations of each other, like "tares" and "tales". With
+    /// `skip_near_duplicates`, a guess that has the same letter in all but one spot as a better
+    /// guess is left out, so that the options differ from each other.
+    pub fn top_k_guesses(
+        &mut self,
+        past_guesses: &[Guess<'l>],
+        k: usize,
+        skip_near_duplicates: bool,
+    ) -> Vec<(&'l Word, f64)> {
+        let near_duplicates =
+            |a: &Word, b: &Word| a.iter().zip(b).filter(|(a, b)| a == b).count() + 1 >= WORD_SIZE;
+
+        let mut top: Vec<(&'l Word, f64)> = Vec::with_capacity(k);
+        for candidate in self.ranked_guesses(past_guesses) {
+            if top.len() == k {
+                break;
+            }
+            if skip_near_duplicates
+                && top
+                    .iter()
+                    .any(|(word, _)| near_duplicates(word, candidate.word))
+            {
+                continue;
+            }
+
+            top.push((candidate.word, candidate.expected_information));
+        }
+
+        top
+    }
+
+    /// How many of the remaining words would be left if guessing `guess` resulted in `mask`,
+    /// which is what `guess` would narrow them down to, without narrowing them down.
+    pub fn simulate(&self, guess: &Word, mask: &[Correctness; WORD_SIZE]) -> usize {
+        let mask = PackedCorrectness::from(*mask);
+
+        self.remaining
+            .keys()
+            .filter(|word| self.check(word, guess) == mask)
+            .count()
+    }
+
+    /// A rough guess of how many more guesses it takes to find the answer, counting the one that
+    /// finds it, for showing progress. Every guess is taken to give `AVERAGE_BITS_PER_GUESS` bits
+    /// of information, so this only depends on how many words could still be the answer, and
+    /// goes down as they do. It's 0 once no word could be the answer.
+    ///
+    /// The remaining words are only narrowed down by `guess`, so this is as of the last guess.
+    pub fn estimated_guesses_remaining(&self) -> f64 {
+        match self.remaining.len() {
+            0 => 0.0,
+            len => 1.0 + (len as f64).log2() / AVERAGE_BITS_PER_GUESS,
+        }
+    }
+
+    fn narrow(&mut self, past_guesses: &[Guess<'l>]) {
         if let Some(last) = past_guesses.last() {
             // We retain words in `remaining` that are guessable after the last word we guessed.
             // Since this process happens once per guess, we don't need to iterate over al past
             // guesses, as those have been filtered out when those past guesses were made.
-            self.remaining.retain(|word, _| {
-                let mask = Correctness::check(word, last.word);
-                for i in 0..WORD_SIZE {
-                    if mask[i] != last.mask[i] {
-                        return false;
-                    }
-                }
+            // Comparing packed masks lets this use the cache too, when there is one.
+            let mask = PackedCorrectness::from(last.mask);
+            let mut remaining = std::mem::take(&mut self.remaining);
+            remaining.retain(|word| self.check(word, last.word) == mask);
+            self.remaining = remaining;
+        }
+    }
+}
 
-                return true;
-            });
+/// A hash of every word of `counts` and its count, which doesn't depend on the order the map
+/// iterates in.
+fn fingerprint(counts: &DictionaryWithCounts) -> u64 {
+    let mut entries = Vec::from_iter(counts.iter().map(|(&word, count)| (word, count.to_bits())));
+    entries.sort_unstable();
+
+    let mut hasher = DefaultHasher::new();
+    entries.hash(&mut hasher);
+    hasher.finish()
+}
+
+impl<'l, M: ScoringMetric> Guesser<'l> for Unoptimized<'l, M> {
+    /// Applying information theory, we try to guess the word. Guessing is a two-step procedure:
+    /// First, we try to limit our space of remaining words to only those that could be possible
+    /// given the last mask. Then, we loop over every word in the dictionary to figure out which
+    /// provides the largest information about the remaining words, and return that.
+    fn guess(&mut self, past_guesses: &[Guess<'l>]) -> &'l Word {
+        let start = self.timings.is_some().then(Instant::now);
+        let (word, score) = self.pick(past_guesses);
+        self.last_score = score;
+
+        if let (Some(timings), Some(start)) = (&mut self.timings, start) {
+            timings.push(start.elapsed());
         }
 
-        let mut best: Option<Candidate> = None;
+        word
+    }
+
+    fn name(&self) -> &'static str {
+        "unoptimized"
+    }
 
-        // We loop over every remaining guess, borrowing words and counts:
-        let total_occurrence_count = self.remaining.values().sum::<f64>();
+    /// The `expected_information` the last guess was picked with, over the remaining words it was
+    /// picked from, weighed by their counts. This is only known for `Entropy`, whose scores are
+    /// bits, and not for an opener or a fallback, which are guessed without scoring them.
+    fn last_expected_information(&self) -> Option<f64> {
+        self.last_score.filter(|_| self.metric.in_bits())
+    }
 
-        for (&word, &occurrence_count) in &self.remaining {
-            // We need to find all the masks that can result from using this word, calculate
-            // the probability of each as the amount of words in the remaining dictionary that
-            // satisfy this mask, take the negative log (the information of the mask), then
-            // calculate the expected value across all masks to get a measure of the quality of
-            // the word.
-            let masks_with_probabilities = self
+    /// Goes back to the words that could be the answer before the first guess. Everything else,
+    /// like the timings of `with_profiling`, carries on from game to game.
+    fn reset(&mut self) {
+        self.remaining.clone_from(&self.initial_remaining);
+        self.last_score = None;
+        self.history_applied = false;
+    }
+}
+
+impl<'l, M: ScoringMetric> Unoptimized<'l, M> {
+    /// Whether the guess after `past_guesses` is the first one of the game, which it isn't once
+    /// `with_history` was given some guesses.
+    fn opening(&self, past_guesses: &[Guess<'l>]) -> bool {
+        past_guesses.is_empty() && !self.history_applied
+    }
+
+    /// The word to guess after `past_guesses`, and its score if it was picked by it.
+    fn pick(&mut self, past_guesses: &[Guess<'l>]) -> (&'l Word, Option<f64>) {
+        let opening = self.opening(past_guesses);
+        if let (Some(opener), true) = (self.opener, opening) {
+            return (opener, None);
+        }
+
+        self.narrow(past_guesses);
+        if self.remaining.is_empty() {
+            return (fallback(self.dictionary.iter().copied()), None);
+        }
+
+        // With one word left there is nothing to find out, and with two, either one splits them
+        // apart with the same information, and nothing else can do better than that while also
+        // possibly being the answer. Every `ScoringMetric` agrees with this, since none of them
+        // can score a split any lower than no split at all. So in both cases the pick comes down
+        // to the tie-breaks between the remaining words, and we can skip scoring the rest of the
+        // dictionary. Every remaining word gets a mask of its own, so that is also what they all
+        // score.
+        if let 1..=2 = self.remaining.len() {
+            let (occurrence_reciprocal, total_answer_prior) = self.totals();
+            let buckets = Vec::from_iter(
+                self.remaining
+                    .values()
+                    .map(|count| count * occurrence_reciprocal),
+            );
+            let score = self.metric.score(&buckets);
+            let best = self
                 .remaining
-                .iter()
-                .map(|(future_guess, future_occurrence_count)| {
-                    (
-                        future_occurrence_count,
-                        Correctness::check(word, &future_guess),
-                    )
-                })
-                .fold(
-                    HashMap::new(),
-                    |mut acc: HashMap<[Correctness; 5], f64>, (future_occurrence_count, mask)| {
-                        // An accumulator entry represents the sum of probabilities of words that
-                        // are possible guesses given that a specific mask (key of acc) results.
-                        let acc_entry = acc.entry(mask).or_insert(0.0);
-                        *acc_entry += future_occurrence_count / total_occurrence_count;
-                        acc
-                    },
-                );
+                .keys()
+                .map(|word| self.scored(word, total_answer_prior, score))
+                .reduce(|best, candidate| self.better(best, candidate))
+                .expect("There is at least one remaining word");
 
-            // Entropy is the expected value of information, where an expected value is defined to
-            // be `Σp(x)⋅x`, and information is defined to be `-log2(p(x))`.
-            // Entropy is a measure of the uniformity of a distribution, and the number of
-            // possibilities within it.
-            let entropy = -masks_with_probabilities
-                .values()
-                .map(|&probability| probability * f64::log2(probability))
-                .sum::<f64>();
-
-            // A new guess is better if no guess was previously made, or if the new guess has more
-            // information, or has the same exact information but is more common.
-            if best.is_none()
-                || entropy > best.unwrap().expected_information
-                || (entropy == best.unwrap().expected_information
-                    && occurrence_count > best.unwrap().occurrence_count)
-            {
-                best = Some(Candidate {
-                    word,
-                    occurrence_count,
-                    expected_information: entropy,
-                });
-            }
+            return (best.word, Some(best.expected_information));
+        }
+
+        let Some(entropy_cache) = self.entropy_cache else {
+            let best = self
+                .best_candidate(opening)
+                .expect("Our guesser has to find at least one word");
+
+            return (best.word, Some(best.expected_information));
+        };
+        entropy_cache.claim(self.cache_config());
+
+        let mut words = Vec::from_iter(
+            self.remaining
+                .iter()
+                .map(|(&word, &count)| (word, count.to_bits())),
+        );
+        words.sort_unstable();
+        let state = (opening, words);
+        if let Some(best) = entropy_cache.entries().get(&state) {
+            return (best.word, Some(best.expected_information));
         }
 
-        let best = best.expect("Our guesser has to find at least one word");
+        // The lock isn't held while scoring, so two threads reaching a new state at once both
+        // score it, and both come up with the same candidate.
+        let best = self
+            .best_candidate(opening)
+            .expect("Our guesser has to find at least one word");
+        entropy_cache.entries().insert(state, best);
 
-        best.word
+        (best.word, Some(best.expected_information))
     }
 }
 
 #[cfg(test)]
 mod tests {
     mod play_wordle {
-        use crate::Unoptimized;
-        use roget::{DictionaryWithCounts, RepresentableAsWord, Word, Wordle};
+        use crate::algorithms::metrics::{
+            Entropy, EstimatedGuesses, ExpectedRemaining, Gini, ScoringMetric, WorstCase,
+        };
+        use crate::algorithms::{EntropyCache, Prior, Unoptimized};
+        use crate::{
+            Correctness, CorrectnessCache, DictionaryLoader, DictionaryWithCounts, Guess, Guesser,
+            RepresentableAsWord, Word, Wordle,
+        };
 
-        const DICTIONARY: &'static str = include_str!("../../dictionary.txt");
+        const DICTIONARY: &str = include_str!("../../dictionary.txt");
 
-        const DICTIONARY_WITH_COUNTS: &'static str = include_str!("../../joined.txt");
+        const DICTIONARY_WITH_COUNTS: &str = include_str!("../../joined.txt");
 
-        #[test]
-        fn unoptimized_tries_highest_information_words() {
-            let wordle: Wordle = Wordle::new(
+        /// Every 40th word of the dictionary, and a game where each of them is both a guess and an
+        /// answer, which is enough words to play real games on while still scoring quickly.
+        fn sampled() -> (Vec<&'static Word>, Wordle<'static>) {
+            let words = Vec::from_iter(
                 DICTIONARY
                     .split_ascii_whitespace()
+                    .step_by(40)
                     .map(|word_str| word_str.as_word()),
             );
+            let wordle = Wordle::new(words.iter().copied());
 
-            let mut dictionary_with_counts_iter = DICTIONARY_WITH_COUNTS.split_ascii_whitespace();
-            let mut dictionary_with_counts: Vec<(&'static Word, usize)> = Vec::new();
+            (words, wordle)
+        }
 
-            while let Some(word) = dictionary_with_counts_iter.next() {
-                let count = dictionary_with_counts_iter.next().unwrap().parse().unwrap();
-                dictionary_with_counts.push((word.as_word(), count))
-            }
+        /// A guesser for `wordle`, counting its possible answers the way the game does.
+        fn unoptimized<'w>(wordle: &'w Wordle<'w>) -> Unoptimized<'w> {
+            Unoptimized::new(
+                wordle.get_dictionary(),
+                wordle.get_possible_answers().clone(),
+            )
+        }
+
+        #[test]
+        fn entropy_buckets_answers_by_the_mask_they_give() {
+            // Guessing "guise" tells all four words apart, since each of the others has one of its
+            // letters somewhere else. Bucketing them by the mask each would get as a guess against
+            // "guise" instead lumps "could" and "wheel" together, and picks "wheel".
+            let words: [&'static Word; 4] = [b"could", b"guise", b"slant", b"wheel"];
+            let wordle = Wordle::new(words);
 
             assert_eq!(
                 wordle.play(
-                    b"moved",
-                    Unoptimized {
-                        dictionary: wordle.get_dictionary(),
-                        remaining: DictionaryWithCounts::from_iter(
-                            dictionary_with_counts.into_iter()
-                        )
-                    }
+                    b"guise",
+                    Unoptimized::new(
+                        wordle.get_dictionary(),
+                        DictionaryWithCounts::from_iter(words.map(|word| (word, 1.0))),
+                    )
                 ),
                 Some(1)
             );
         }
+
+        #[test]
+        fn unoptimized_tries_highest_information_words() {
+            let wordle = Wordle::from_str_list(DICTIONARY);
+
+            let (dictionary_with_counts, _) = DictionaryLoader::from_counts(DICTIONARY_WITH_COUNTS);
+
+            // The opener is "tares", after which "limed" and "meved" narrow it down to "moved".
+            assert_eq!(
+                wordle.play(
+                    b"moved",
+                    Unoptimized::new(wordle.get_dictionary(), dictionary_with_counts)
+                ),
+                Some(4)
+            );
+        }
+
+        #[test]
+        fn guesses_words_that_are_never_answers() {
+            let answers: [&Word; 4] = [b"hello", b"jello", b"cello", b"mello"];
+            let wordle = Wordle::with_answers(
+                [b"chjmx"],
+                DictionaryWithCounts::from_iter(answers.map(|word| (word, 1.0))),
+            );
+            let mut guesser = unoptimized(&wordle);
+
+            // "chjmx" can't be the answer, but it tells all four answers apart, while guessing any
+            // of the answers leaves the other three in a single bucket.
+            assert_eq!(guesser.guess(&[]), b"chjmx");
+            for answer in answers {
+                let guesser = unoptimized(&wordle);
+                assert_eq!(wordle.play(answer, guesser), Some(2));
+            }
+        }
+
+        #[test]
+        fn exact_ties_go_to_the_word_first_alphabetically() {
+            // Either word tells the two apart and is just as common, so only the word itself can
+            // break the tie. Every map gets its own random iteration order, so building many of
+            // them, in both insertion orders, would catch a tie-break that depends on it.
+            for _ in 0..20 {
+                for words in [[b"fghij", b"abcde"], [b"abcde", b"fghij"]] {
+                    let wordle = Wordle::new(words);
+                    let mut guesser = Unoptimized::new(
+                        wordle.get_dictionary(),
+                        DictionaryWithCounts::from_iter(words.map(|word| (word, 1.0))),
+                    );
+
+                    assert_eq!(guesser.guess(&[]), b"abcde");
+                }
+            }
+        }
+
+        #[test]
+        fn prefers_candidates_when_few_remain() {
+            let answers: [&Word; 4] = [b"hello", b"jello", b"cello", b"mello"];
+            let wordle = Wordle::with_answers(
+                [b"chjmx"],
+                DictionaryWithCounts::from_iter(answers.map(|word| (word, 1.0))),
+            );
+            let guesser =
+                |threshold| unoptimized(&wordle).with_prefer_candidates_when_few(threshold);
+
+            // With more words left than the threshold, "chjmx" still tells them apart best.
+            assert_eq!(guesser(3).guess(&[]), b"chjmx");
+            for answer in answers {
+                let mut guesser = guesser(4);
+                assert!(answers.contains(&guesser.guess(&[])));
+                assert!(guesser
+                    .ranked_guesses(&[])
+                    .iter()
+                    .all(|candidate| candidate.possible_answer));
+                assert!(wordle.play(answer, guesser).is_some());
+            }
+        }
+
+        #[test]
+        fn games_in_the_same_state_share_their_entropy() {
+            use std::sync::atomic::Ordering;
+
+            let answers: [&Word; 4] = [b"hello", b"jello", b"cello", b"mello"];
+            let wordle = Wordle::with_answers(
+                [b"chjmx", b"crane"],
+                DictionaryWithCounts::from_iter(answers.map(|word| (word, 1.0))),
+            );
+            let cache = EntropyCache::new();
+            let guesser = unoptimized(&wordle).with_entropy_cache(&cache);
+
+            let mut first = guesser.clone();
+            assert_eq!(first.guess(&[]), b"chjmx");
+            assert_eq!(cache.len(), 1);
+
+            let mut second = guesser.clone();
+            second.entropy_calls.store(0, Ordering::Relaxed);
+            assert_eq!(second.guess(&[]), b"chjmx");
+            assert_eq!(second.entropy_calls.load(Ordering::Relaxed), 0);
+
+            // A different state misses the cache.
+            let mut fewer = Unoptimized::new(
+                wordle.get_dictionary(),
+                DictionaryWithCounts::from_iter(answers[..3].iter().map(|&word| (word, 1.0))),
+            )
+            .with_entropy_cache(&cache);
+            fewer.guess(&[]);
+            assert_eq!(cache.len(), 2);
+        }
+
+        #[test]
+        #[should_panic(expected = "shared by guessers set up alike")]
+        fn entropy_caches_refuse_a_guesser_with_another_weight() {
+            let answers: [&Word; 4] = [b"hello", b"jello", b"cello", b"mello"];
+            let wordle = Wordle::with_answers(
+                [b"chjmx", b"crane"],
+                DictionaryWithCounts::from_iter(answers.map(|word| (word, 1.0))),
+            );
+            let cache = EntropyCache::new();
+            let guesser = unoptimized(&wordle);
+
+            guesser.clone().with_entropy_cache(&cache).guess(&[]);
+            guesser
+                .with_entropy_cache(&cache)
+                .with_weight(5.0)
+                .guess(&[]);
+        }
+
+        #[test]
+        #[should_panic(expected = "shared by guessers set up alike")]
+        fn entropy_caches_refuse_a_guesser_with_another_metric() {
+            let answers: [&Word; 4] = [b"hello", b"jello", b"cello", b"mello"];
+            let wordle = Wordle::with_answers(
+                [b"chjmx", b"crane"],
+                DictionaryWithCounts::from_iter(answers.map(|word| (word, 1.0))),
+            );
+            let cache = EntropyCache::new();
+            let guesser = unoptimized(&wordle).with_entropy_cache(&cache);
+
+            guesser.clone().guess(&[]);
+            guesser.with_metric(WorstCase).guess(&[]);
+        }
+
+        #[test]
+        fn entropy_metric_plays_as_before() {
+            let dictionary_with_counts = DictionaryWithCounts::from_iter(
+                DICTIONARY_WITH_COUNTS.lines().step_by(20).map(|line| {
+                    let (word, count) = line.split_once(' ').unwrap();
+                    (word.as_word(), count.parse().unwrap())
+                }),
+            );
+            let wordle = Wordle::new(dictionary_with_counts.keys().copied());
+            let guesser = Unoptimized::new(wordle.get_dictionary(), dictionary_with_counts)
+                .with_metric(Entropy);
+
+            // These are the games `Unoptimized` played before its metric could be changed.
+            let games: [&[&Word]; 4] = [
+                &[b"pores", b"wails", b"aahed"],
+                &[b"pores", b"withy", b"chins"],
+                &[b"pores", b"flite"],
+                &[b"pores", b"group", b"tromp"],
+            ];
+            for game in games {
+                let answer = game[game.len() - 1];
+                let record = wordle.play_recorded(answer, guesser.clone());
+                let guesses = Vec::from_iter(record.guesses.iter().map(|guess| guess.word));
+
+                assert_eq!(guesses, game);
+            }
+        }
+
+        #[test]
+        fn gini_ranks_openers_close_to_entropy() {
+            let dictionary_with_counts = DictionaryWithCounts::from_iter(
+                DICTIONARY_WITH_COUNTS.lines().step_by(50).map(|line| {
+                    let (word, count) = line.split_once(' ').unwrap();
+                    (word.as_word(), count.parse().unwrap())
+                }),
+            );
+            let wordle = Wordle::new(dictionary_with_counts.keys().copied());
+            let guesser = Unoptimized::new(wordle.get_dictionary(), dictionary_with_counts);
+            fn top<'l, M: ScoringMetric>(mut guesser: Unoptimized<'l, M>) -> Vec<&'l Word> {
+                Vec::from_iter(
+                    guesser
+                        .ranked_guesses(&[])
+                        .iter()
+                        .take(10)
+                        .map(|candidate| candidate.word),
+                )
+            }
+
+            // Both open with "paise", and share 6 of their 10 best openers.
+            let entropy = top(guesser.clone());
+            let gini = top(guesser.clone().with_metric(Gini));
+            assert_eq!(entropy[0], b"paise");
+            assert_eq!(gini[0], b"paise");
+            assert_eq!(entropy.iter().filter(|word| gini.contains(word)).count(), 6);
+        }
+
+        #[test]
+        fn every_metric_splits_when_no_answer_can() {
+            let answers: [&Word; 4] = [b"hello", b"jello", b"cello", b"mello"];
+            let wordle = Wordle::with_answers(
+                [b"chjmx"],
+                DictionaryWithCounts::from_iter(answers.map(|word| (word, 1.0))),
+            );
+            let guesser = unoptimized(&wordle);
+
+            assert_eq!(
+                guesser.clone().with_metric(ExpectedRemaining).guess(&[]),
+                b"chjmx"
+            );
+            assert_eq!(guesser.clone().with_metric(WorstCase).guess(&[]), b"chjmx");
+            assert_eq!(guesser.clone().with_metric(Gini).guess(&[]), b"chjmx");
+            assert_eq!(
+                guesser
+                    .clone()
+                    .with_metric(EstimatedGuesses::new(answers.len()))
+                    .guess(&[]),
+                b"chjmx"
+            );
+            for answer in answers {
+                assert_eq!(
+                    wordle.play(answer, guesser.clone().with_metric(WorstCase)),
+                    Some(2)
+                );
+            }
+        }
+
+        #[test]
+        fn uniform_prior_makes_every_answer_as_likely() {
+            let words: [(&'static Word, f64); 5] = [
+                (b"hello", 1000.0),
+                (b"jello", 1.0),
+                (b"cello", 10.0),
+                (b"mello", 1.0),
+                (b"chjmx", 5.0),
+            ];
+            let wordle = Wordle::new(words.map(|(word, _)| word));
+            let guesser =
+                Unoptimized::new(wordle.get_dictionary(), words.into()).with_prior(Prior::Uniform);
+
+            assert!(guesser.remaining.values().all(|&count| count == 1.0));
+            // Guessing "hello" now finds it a fifth of the time, tells "chjmx" apart by its "h"
+            // another fifth, and leaves the other three -ello words otherwise.
+            let hello = -(2.0 * 0.2 * f64::log2(0.2) + 0.6 * f64::log2(0.6));
+            assert!((guesser.expected_information(b"hello") - hello).abs() < 1e-12);
+
+            let raw =
+                Unoptimized::new(wordle.get_dictionary(), words.into()).with_prior(Prior::Raw);
+            assert_eq!(raw.remaining, DictionaryWithCounts::from(words));
+        }
+
+        #[test]
+        fn sigmoid_rank_prior_follows_the_rank() {
+            let words: [(&'static Word, f64); 3] =
+                [(b"hello", 1000.0), (b"world", 10.0), (b"moved", 1.0)];
+            let wordle = Wordle::new(words.map(|(word, _)| word));
+            let guesser = Unoptimized::new(wordle.get_dictionary(), words.into()).with_prior(
+                Prior::SigmoidRank {
+                    steepness: 1.0,
+                    midpoint: 1.0,
+                },
+            );
+
+            assert!(guesser.remaining[b"hello"] > guesser.remaining[b"world"]);
+            assert_eq!(guesser.remaining[b"world"], 0.5);
+            assert!(guesser.remaining[b"world"] > guesser.remaining[b"moved"]);
+        }
+
+        #[test]
+        fn priors_go_by_the_counts_the_guesser_was_built_with() {
+            let words: [(&'static Word, f64); 3] =
+                [(b"hello", 1000.0), (b"world", 10.0), (b"moved", 1.0)];
+            let wordle = Wordle::new(words.map(|(word, _)| word));
+            let new = || Unoptimized::new(wordle.get_dictionary(), words.into());
+            let sigmoid = Prior::SigmoidRank {
+                steepness: 1.0,
+                midpoint: 1.0,
+            };
+
+            let once = new().with_prior(sigmoid);
+            let built_with =
+                Unoptimized::new_with_prior(wordle.get_dictionary(), words.into(), sigmoid);
+            assert_eq!(built_with.remaining, once.remaining);
+            assert_eq!(
+                new().with_prior(sigmoid).with_prior(sigmoid).remaining,
+                once.remaining
+            );
+            let after_uniform = new().with_prior(Prior::Uniform).with_prior(sigmoid);
+            assert_eq!(after_uniform.remaining, once.remaining);
+            assert_eq!(after_uniform.initial_remaining, once.initial_remaining);
+
+            let prior_first = new().with_prior(Prior::Uniform).with_weight(1.0);
+            let weight_first = new().with_weight(1.0).with_prior(Prior::Uniform);
+            assert_eq!(
+                prior_first.answer_priors,
+                new().with_weight(1.0).answer_priors
+            );
+            assert_eq!(prior_first.answer_priors, weight_first.answer_priors);
+            assert_eq!(prior_first.remaining, weight_first.remaining);
+        }
+
+        #[test]
+        fn answer_prior_falls_off_with_frequency_rank() {
+            let (dictionary_with_counts, _) = DictionaryLoader::from_counts(DICTIONARY_WITH_COUNTS);
+            let wordle = Wordle::new(dictionary_with_counts.keys().copied());
+
+            let guesser =
+                Unoptimized::new(wordle.get_dictionary(), dictionary_with_counts).with_weight(1.0);
+
+            // "which" is one of the most common words, while "aalii" is one of the rarest.
+            assert!(guesser.answer_priors[b"which"] > 0.99);
+            assert!(guesser.answer_priors[b"aalii"] < 0.01);
+        }
+
+        #[cfg(feature = "rayon")]
+        #[test]
+        fn parallel_and_serial_pick_the_same_candidate() {
+            let dictionary_with_counts = DictionaryWithCounts::from_iter(
+                DICTIONARY_WITH_COUNTS.lines().step_by(50).map(|line| {
+                    let (word, count) = line.split_once(' ').unwrap();
+                    (word.as_word(), count.parse().unwrap())
+                }),
+            );
+            let wordle = Wordle::new(dictionary_with_counts.keys().copied());
+
+            for weight in [0.0, 10.0] {
+                let mut guesser =
+                    Unoptimized::new(wordle.get_dictionary(), dictionary_with_counts.clone())
+                        .with_weight(weight);
+
+                assert_eq!(
+                    guesser
+                        .best_candidate_parallel(false)
+                        .map(|candidate| candidate.word),
+                    guesser
+                        .best_candidate_serial(false)
+                        .map(|candidate| candidate.word)
+                );
+            }
+        }
+
+        #[test]
+        fn cached_and_uncached_filters_agree() {
+            let (words, wordle) = sampled();
+            let remaining = DictionaryWithCounts::from_iter(words.iter().map(|&word| (word, 1.0)));
+            let cache = CorrectnessCache::new(&words);
+
+            for &answer in words.iter().step_by(23) {
+                let past = [Guess {
+                    word: words[0],
+                    mask: Correctness::check(answer, words[0]),
+                }];
+
+                let mut cached =
+                    Unoptimized::new(wordle.get_dictionary(), remaining.clone()).with_cache(&cache);
+                let mut uncached = Unoptimized::new(wordle.get_dictionary(), remaining.clone());
+                cached.guess(&past);
+                uncached.guess(&past);
+
+                assert!(cached.remaining.contains_key(answer));
+                assert_eq!(cached.remaining, uncached.remaining);
+            }
+        }
+
+        #[test]
+        fn profiling_records_a_timing_per_guess() {
+            let words: [(&'static Word, f64); 3] =
+                [(b"hello", 1.0), (b"world", 1.0), (b"which", 1.0)];
+            let wordle = Wordle::new(words.map(|(word, _)| word));
+
+            let mut guesser = Unoptimized::new(wordle.get_dictionary(), words.into())
+                .with_opener(b"world")
+                .with_profiling();
+            assert!(guesser.timings().is_empty());
+
+            let past = [Guess {
+                word: b"world",
+                mask: Correctness::check(b"hello", b"world"),
+            }];
+            guesser.guess(&[]);
+            assert_eq!(guesser.timings().len(), 1);
+            guesser.guess(&past);
+            assert_eq!(guesser.timings().len(), 2);
+
+            let mut unprofiled = Unoptimized::new(wordle.get_dictionary(), words.into());
+            unprofiled.guess(&[]);
+            assert!(unprofiled.timings().is_empty());
+        }
+
+        #[test]
+        fn mid_game_clones_guess_alike() {
+            let (words, wordle) = sampled();
+            let answer = words[100];
+
+            let mut guesser = unoptimized(&wordle);
+            let mut past = Vec::new();
+            let word = guesser.guess(&past);
+            past.push(Guess {
+                word,
+                mask: Correctness::check(answer, word),
+            });
+            guesser.guess(&past);
+
+            let mut clone = guesser.clone();
+            let word = guesser.guess(&past);
+            past.push(Guess {
+                word,
+                mask: Correctness::check(answer, word),
+            });
+
+            assert_eq!(clone.guess(&past), guesser.guess(&past));
+            assert_eq!(clone.remaining, guesser.remaining);
+        }
+
+        #[test]
+        fn expected_information_matches_hand_computed_entropy() {
+            let words: [(&'static Word, f64); 5] = [
+                (b"hello", 1.0),
+                (b"jello", 1.0),
+                (b"cello", 1.0),
+                (b"mello", 1.0),
+                (b"chjmx", 0.0),
+            ];
+            let wordle = Wordle::new(words.map(|(word, _)| word));
+            let guesser = Unoptimized::new(wordle.get_dictionary(), words.into());
+
+            // "hello" is the answer a quarter of the time, and leaves the other three -ello words
+            // otherwise, which is `-(1/4⋅log2(1/4) + 3/4⋅log2(3/4))` bits.
+            let hello = -(0.25 * f64::log2(0.25) + 0.75 * f64::log2(0.75));
+            assert!((guesser.expected_information(b"hello") - hello).abs() < 1e-12);
+            // "chjmx" tells all four apart, which is two bits.
+            assert!((guesser.expected_information(b"chjmx") - 2.0).abs() < 1e-12);
+        }
+
+        #[test]
+        fn ranked_guesses_start_with_the_guess() {
+            let (words, wordle) = sampled();
+            let mut guesser = unoptimized(&wordle);
+
+            let ranked = guesser.ranked_guesses(&[]);
+            assert_eq!(ranked.len(), words.len());
+            assert!(ranked
+                .windows(2)
+                .all(|pair| pair[0].expected_information >= pair[1].expected_information));
+            assert_eq!(ranked[0].word, guesser.guess(&[]));
+
+            let past = [Guess {
+                word: ranked[0].word,
+                mask: Correctness::check(words[100], ranked[0].word),
+            }];
+            let ranked = guesser.ranked_guesses(&past);
+            assert_eq!(ranked[0].word, guesser.guess(&past));
+        }
+
+        #[test]
+        fn reset_guessers_play_like_fresh_ones() {
+            let (words, wordle) = sampled();
+            let fresh = || unoptimized(&wordle);
+
+            let mut reused = fresh();
+            for &answer in [words[100], words[200]].iter() {
+                reused.reset();
+                let record = wordle.play_recorded(answer, &mut reused);
+                let expected = wordle.play_recorded(answer, fresh());
+
+                assert_eq!(
+                    Vec::from_iter(record.guesses.iter().map(|guess| guess.word)),
+                    Vec::from_iter(expected.guesses.iter().map(|guess| guess.word))
+                );
+            }
+            reused.reset();
+            assert_eq!(reused.remaining, *wordle.get_possible_answers());
+        }
+
+        #[test]
+        fn simulate_counts_what_guessing_leaves() {
+            let (words, wordle) = sampled();
+
+            for &answer in words.iter().step_by(37) {
+                let mut guesser = unoptimized(&wordle);
+                let word = guesser.guess(&[]);
+                let past = [Guess::new(answer, word)];
+
+                let simulated = guesser.simulate(word, &past[0].mask);
+                assert_eq!(guesser.remaining.len(), words.len());
+                guesser.guess(&past);
+                assert_eq!(simulated, guesser.remaining.len());
+            }
+        }
+
+        #[test]
+        fn estimated_guesses_go_down_with_the_remaining_words() {
+            let (words, wordle) = sampled();
+            let answer = words[123];
+            let mut guesser = unoptimized(&wordle);
+
+            // Every guess that misses rules some words out, which takes the estimate down.
+            let mut past = Vec::new();
+            let mut estimate = guesser.estimated_guesses_remaining();
+            loop {
+                let word = guesser.guess(&past);
+                if !past.is_empty() {
+                    assert!(guesser.estimated_guesses_remaining() < estimate);
+                }
+                estimate = guesser.estimated_guesses_remaining();
+                if word == answer {
+                    break;
+                }
+                past.push(Guess::new(answer, word));
+            }
+
+            // The last word left takes exactly one more guess, and none takes none.
+            let mut one_left = Unoptimized::new(
+                wordle.get_dictionary(),
+                DictionaryWithCounts::from_iter([(answer, 1.0)]),
+            );
+            assert_eq!(one_left.estimated_guesses_remaining(), 1.0);
+            one_left.guess(&[Guess::new(words[0], answer)]);
+            assert_eq!(one_left.estimated_guesses_remaining(), 0.0);
+        }
+
+        #[test]
+        fn top_k_guesses_are_the_best_few() {
+            let words = Vec::from_iter(
+                DICTIONARY
+                    .split_ascii_whitespace()
+                    .step_by(20)
+                    .map(|word_str| word_str.as_word()),
+            );
+            let wordle = Wordle::new(words.iter().copied());
+            let mut guesser = unoptimized(&wordle);
+
+            let top = guesser.top_k_guesses(&[], 8, false);
+            assert_eq!(top.len(), 8);
+            assert_eq!(top[0].0, guesser.clone().guess(&[]));
+            assert!(top.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+
+            // "mores" is "pores" with another first letter, so it makes way for the next guess.
+            let words = Vec::from_iter(top.iter().map(|&(word, _)| word));
+            assert_eq!(words[2..4], [b"pores", b"mores"]);
+
+            let distinct = guesser.top_k_guesses(&[], 8, true);
+            assert_eq!(distinct.len(), 8);
+            assert!(!distinct.iter().any(|&(word, _)| word == b"mores"));
+            assert_eq!(distinct[..7], [&top[..3], &top[4..]].concat());
+        }
+
+        #[test]
+        fn from_wordle_weighs_answers_by_the_counts_of_the_game() {
+            let counts = DictionaryWithCounts::from([(b"hello", 3.0), (b"world", 2.0)]);
+            let wordle = Wordle::with_answers(
+                [b"chjmx"],
+                DictionaryWithCounts::from([(b"hello", 1.0), (b"world", 1.0), (b"moved", 1.0)]),
+            )
+            .with_counts(counts);
+            let guesser = Unoptimized::from_wordle(&wordle);
+
+            // "moved" has no count in the game, so it keeps the one it has as an answer.
+            assert_eq!(
+                guesser.remaining,
+                DictionaryWithCounts::from([(b"hello", 3.0), (b"world", 2.0), (b"moved", 1.0)])
+            );
+            assert!(wordle.get_dictionary().contains(b"chjmx"));
+            assert_eq!(
+                Unoptimized::from_wordle(&Wordle::new([b"hello"])).remaining,
+                DictionaryWithCounts::from([(b"hello", 1.0)])
+            );
+        }
+
+        #[test]
+        fn bitset_guesses_like_the_map() {
+            let (words, wordle) = sampled();
+            let map = unoptimized(&wordle);
+            let mut bitset = map.clone().with_bitset();
+
+            for &answer in words.iter().step_by(23) {
+                bitset.reset();
+                let record = wordle.play_recorded(answer, &mut bitset);
+                let expected = wordle.play_recorded(answer, map.clone());
+
+                assert_eq!(
+                    Vec::from_iter(record.guesses.iter().map(|guess| guess.word)),
+                    Vec::from_iter(expected.guesses.iter().map(|guess| guess.word))
+                );
+                assert!(bitset.remaining.contains_key(answer));
+            }
+            bitset.reset();
+            assert_eq!(bitset.remaining, *wordle.get_possible_answers());
+        }
+
+        #[test]
+        fn bitset_looks_masks_up_by_index() {
+            let (words, wordle) = sampled();
+            let map = unoptimized(&wordle);
+            let cache = CorrectnessCache::new(&words);
+
+            let cache_first = map.clone().with_cache(&cache).with_bitset();
+            let bitset_first = map.clone().with_bitset().with_cache(&cache);
+            let with_prior = bitset_first.clone().with_prior(Prior::Uniform);
+            for guesser in [&cache_first, &bitset_first, &with_prior] {
+                assert!(guesser.remaining.is_indexed());
+                assert!(guesser.initial_remaining.is_indexed());
+            }
+            for &word in words.iter().step_by(7) {
+                let expected = map.expected_information(word);
+                assert!((cache_first.expected_information(word) - expected).abs() < 1e-12);
+            }
+
+            // A cache missing some of the answers can't be indexed into, and is looked up by word.
+            let few_answers = CorrectnessCache::builder(&words)
+                .answers(&words[..10])
+                .build();
+            let unindexed = map.clone().with_bitset().with_cache(&few_answers);
+            assert!(!unindexed.remaining.is_indexed());
+            assert_eq!(unindexed.clone().guess(&[]), map.clone().guess(&[]));
+        }
+
+        #[test]
+        fn falls_back_without_scoring_when_no_answer_remains() {
+            use std::sync::atomic::Ordering;
+
+            let words: [(&'static Word, f64); 2] = [(b"world", 1.0), (b"hello", 1.0)];
+            let wordle = Wordle::new(words.map(|(word, _)| word));
+            let mut guesser = Unoptimized::new(wordle.get_dictionary(), words.into());
+
+            // No word of the dictionary is the answer that produced this mask.
+            let past = [Guess::new(b"crane", b"hello")];
+
+            assert_eq!(guesser.guess(&past), b"hello");
+            assert!(guesser.remaining.is_empty());
+            assert_eq!(guesser.entropy_calls.load(Ordering::Relaxed), 0);
+        }
+
+        #[test]
+        fn last_words_are_guessed_without_scoring() {
+            use std::sync::atomic::Ordering;
+
+            let wordle = Wordle::with_answers(
+                [b"chjmx", b"crane", b"slate"],
+                DictionaryWithCounts::from([(b"hello", 1.0), (b"jello", 2.0)]),
+            );
+
+            let mut guesser = Unoptimized::new(
+                wordle.get_dictionary(),
+                DictionaryWithCounts::from([(b"hello", 1.0)]),
+            );
+            assert_eq!(guesser.guess(&[]), b"hello");
+            assert_eq!(guesser.entropy_calls.load(Ordering::Relaxed), 0);
+
+            // With two words left, scoring the whole dictionary picks the more common of them,
+            // and so does skipping it.
+            let mut guesser = unoptimized(&wordle);
+            let ranked = guesser.clone().ranked_guesses(&[]);
+            assert_eq!(ranked[0].word, b"jello");
+            guesser.entropy_calls.store(0, Ordering::Relaxed);
+            assert_eq!(guesser.guess(&[]), b"jello");
+            assert_eq!(guesser.entropy_calls.load(Ordering::Relaxed), 0);
+        }
+
+        #[test]
+        fn last_expected_information_is_what_the_guess_was_picked_by() {
+            use std::sync::atomic::Ordering;
+
+            let answers: [&Word; 4] = [b"hello", b"jello", b"cello", b"mello"];
+            let wordle = Wordle::with_answers(
+                [b"chjmx", b"crane"],
+                DictionaryWithCounts::from_iter(answers.map(|word| (word, 1.0))),
+            );
+            let new = || unoptimized(&wordle);
+
+            let mut guesser = new();
+            assert_eq!(guesser.last_expected_information(), None);
+            assert_eq!(guesser.guess(&[]), b"chjmx");
+            // "chjmx" tells all four apart, which is two bits, and that is known without scoring
+            // it again.
+            let calls = guesser.entropy_calls.load(Ordering::Relaxed);
+            let information = guesser.last_expected_information().unwrap();
+            assert!((information - 2.0).abs() < 1e-12);
+            assert_eq!(guesser.entropy_calls.load(Ordering::Relaxed), calls);
+
+            // An opener isn't scored, and other metrics don't score in bits.
+            let mut opener = new().with_opener(b"crane");
+            assert_eq!(opener.guess(&[]), b"crane");
+            assert_eq!(opener.last_expected_information(), None);
+            let mut worst_case = new().with_metric(WorstCase);
+            worst_case.guess(&[]);
+            assert_eq!(worst_case.last_expected_information(), None);
+        }
+
+        #[test]
+        fn precomputed_opener_is_guessed_first() {
+            let words: [(&'static Word, f64); 4] = [
+                (b"hello", 1.0),
+                (b"world", 1.0),
+                (b"moved", 1.0),
+                (b"which", 1.0),
+            ];
+            let wordle = Wordle::new(words.map(|(word, _)| word));
+
+            let record = wordle.play_recorded(
+                b"moved",
+                Unoptimized::new(
+                    wordle.get_dictionary(),
+                    DictionaryWithCounts::from_iter(words),
+                )
+                .with_opener(b"which"),
+            );
+
+            assert_eq!(record.guesses[0].word, b"which");
+            assert_eq!(record.outcome.map(|guesses| guesses > 1), Some(true));
+        }
+
+        #[test]
+        fn history_narrows_down_by_every_guess() {
+            let (words, wordle) = sampled();
+            let answer = words[200];
+            let past = [Guess::new(answer, b"crane"), Guess::new(answer, b"sloth")];
+
+            let mut guesser = unoptimized(&wordle).with_history(&past);
+
+            let expected = Guess::filter_candidates(&past, &words);
+            assert!(expected.len() < Guess::filter_candidates(&past[1..], &words).len());
+            let mut remaining = Vec::from_iter(guesser.remaining.keys());
+            remaining.sort_unstable();
+            assert_eq!(remaining, expected);
+
+            // Guessing with the same guesses again narrows nothing more down.
+            let next = guesser.clone().guess(&[]);
+            assert_eq!(guesser.guess(&past), next);
+            assert_eq!(guesser.remaining.len(), expected.len());
+        }
+
+        #[test]
+        fn history_means_the_opener_was_already_guessed() {
+            let (words, wordle) = sampled();
+            let answer = words[200];
+            let past = [Guess::new(answer, b"crane")];
+            let guesser = unoptimized(&wordle);
+            let next = guesser.clone().with_history(&past).guess(&[]);
+            assert_ne!(next, b"sloth");
+
+            // Neither the opener nor the pool of openers is used past the first guess.
+            let pool = [b"sloth" as &Word];
+            let mut guesser = guesser.with_opener(b"sloth").with_history(&past);
+            assert_eq!(guesser.clone().guess(&[]), next);
+            assert_eq!(guesser.clone().with_opener_pool(&pool).guess(&[]), next);
+
+            // Starting over, the opener is the first guess again.
+            guesser.reset();
+            assert_eq!(guesser.guess(&[]), b"sloth");
+        }
+
+        #[test]
+        fn opener_is_picked_from_the_pool() {
+            use std::sync::atomic::Ordering;
+
+            let (words, wordle) = sampled();
+            let guesser = unoptimized(&wordle);
+            // The pool leaves out the best opener, so that it has to be skipped.
+            let best = guesser.clone().guess(&[]);
+            let pool = Vec::from_iter(words.iter().copied().filter(|&word| word != best).take(8));
+            let mut guesser = guesser.with_opener_pool(&pool);
+            guesser.entropy_calls.store(0, Ordering::Relaxed);
+
+            let opener = guesser.guess(&[]);
+            assert!(pool.contains(&opener));
+            assert_eq!(guesser.entropy_calls.load(Ordering::Relaxed), pool.len());
+            let expected = pool
+                .iter()
+                .map(|&word| guesser.expected_information(word))
+                .fold(f64::NEG_INFINITY, f64::max);
+            assert_eq!(guesser.expected_information(opener), expected);
+
+            // Later guesses are scored from the whole dictionary again.
+            let answer = words[77];
+            guesser.entropy_calls.store(0, Ordering::Relaxed);
+            guesser.guess(&[Guess::new(answer, opener)]);
+            assert!(guesser.remaining.len() > 2);
+            assert_eq!(guesser.entropy_calls.load(Ordering::Relaxed), words.len());
+        }
+
+        #[test]
+        fn cached_openers_from_the_pool_are_only_reused_as_openers() {
+            let mut words = Vec::from_iter(
+                DICTIONARY
+                    .split_ascii_whitespace()
+                    .step_by(40)
+                    .map(|word_str| word_str.as_word())
+                    .filter(|word| !word.contains(&b'x')),
+            );
+            let answers = DictionaryWithCounts::from_iter(words.iter().map(|&word| (word, 1.0)));
+            // Guessing "xxxxx" rules nothing out, so the game is back where it started after it.
+            words.push(b"xxxxx");
+            let wordle = Wordle::new(words.iter().copied());
+            let guesser = Unoptimized::new(wordle.get_dictionary(), answers);
+            let best = guesser.clone().guess(&[]);
+            let pool = Vec::from_iter(words.iter().copied().filter(|&word| word != best).take(8));
+            let cache = EntropyCache::new();
+            let mut guesser = guesser.with_opener_pool(&pool).with_entropy_cache(&cache);
+
+            assert!(pool.contains(&guesser.guess(&[])));
+            assert_eq!(guesser.guess(&[Guess::new(words[0], b"xxxxx")]), best);
+            assert_eq!(cache.len(), 2);
+        }
+
+        #[test]
+        fn unoptimized_guesses_with_realistic_counts() {
+            let dictionary_with_counts = DictionaryWithCounts::from_iter(
+                DICTIONARY_WITH_COUNTS.lines().step_by(50).map(|line| {
+                    let (word, count) = line.split_once(' ').unwrap();
+                    (word.as_word(), count.parse().unwrap())
+                }),
+            );
+            let wordle = Wordle::new(dictionary_with_counts.keys().copied());
+
+            let mut guesser = Unoptimized::new(wordle.get_dictionary(), dictionary_with_counts);
+
+            assert_eq!(guesser.guess(&[]), b"paise");
+        }
     }
 }
@@ -0,0 +1,358 @@
+use crate::{Correctness, Guess, Strictness, Word, WORD_SIZE};
+use std::collections::{HashMap, HashSet};
+
+/// What a set of past guesses says about the letters of the answer, gathered in one place instead
+/// of being worked out again from the masks wherever it's needed.
+///
+/// Duplicate letters are where this gets subtle. Every green or yellow copy of a letter in a guess
+/// stands for a separate copy of it in the answer, so they give a minimum count. A gray copy of a
+/// letter that also has green or yellow copies in the same guess doesn't mean the letter is
+/// absent, but that the answer has exactly as many copies as were green or yellow, none of them
+/// where the gray copy is.
+///
+/// A word matches the constraints of some guesses exactly when every one of those guesses would
+/// have gotten its mask had the word been the answer, so this is what candidates are filtered by.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Constraints<const N: usize = WORD_SIZE> {
+    /// The letter known to be at each position, if any.
+    pub greens: [Option<u8>; N],
+    /// Letters known to be somewhere in the answer.
+    pub present: HashSet<u8>,
+    /// Letters known not to be anywhere in the answer.
+    pub absent: HashSet<u8>,
+    /// The letters known not to be at each position, because a guess had them there and they came
+    /// back yellow or gray.
+    pub forbidden_positions: [HashSet<u8>; N],
+    /// The fewest copies of each present letter the answer can have.
+    pub min_counts: HashMap<u8, usize>,
+    /// The most copies of a present letter the answer can have, for the letters a gray copy
+    /// pinned down exactly.
+    pub max_counts: HashMap<u8, usize>,
+}
+
+impl<const N: usize> Default for Constraints<N> {
+    fn default() -> Self {
+        Self {
+            greens: [None; N],
+            present: HashSet::new(),
+            absent: HashSet::new(),
+            forbidden_positions: std::array::from_fn(|_| HashSet::new()),
+            min_counts: HashMap::new(),
+            max_counts: HashMap::new(),
+        }
+    }
+}
+
+impl<const N: usize> Constraints<N> {
+    /// Gathers what every guess of `past` says. Masks that contradict each other give constraints
+    /// that no word matches, and so do masks that `Correctness::check` never gives, like a gray
+    /// copy of a letter before a yellow copy of it.
+    pub fn from_guesses(past: &[Guess<'_, N>]) -> Self {
+        let mut constraints = Self::default();
+
+        for guess in past {
+            let mut counts: HashMap<u8, usize> = HashMap::new();
+            let mut grays = HashSet::new();
+
+            for (i, (&letter, &correctness)) in guess.word.iter().zip(&guess.mask).enumerate() {
+                match correctness {
+                    Correctness::Correct => {
+                        // Two different greens in the same spot can't both hold, so both letters
+                        // are forbidden there, whichever of them the spot ends up given.
+                        if let Some(green) = constraints.greens[i].filter(|&green| green != letter)
+                        {
+                            constraints.forbidden_positions[i].extend([green, letter]);
+                        }
+                        constraints.greens[i] = Some(letter);
+                        *counts.entry(letter).or_default() += 1;
+                    }
+                    Correctness::Misplaced => {
+                        // `check` hands yellows out to the leftmost copies of a letter first, so
+                        // a yellow after a gray copy of its letter can't happen either, which
+                        // marking the present letter absent makes sure of.
+                        if grays.contains(&letter) {
+                            constraints.absent.insert(letter);
+                        }
+                        constraints.forbidden_positions[i].insert(letter);
+                        *counts.entry(letter).or_default() += 1;
+                    }
+                    // A gray copy of a letter that is elsewhere in the answer still says the
+                    // letter isn't here.
+                    Correctness::Wrong => {
+                        constraints.forbidden_positions[i].insert(letter);
+                        grays.insert(letter);
+                    }
+                }
+            }
+
+            for (&letter, &count) in &counts {
+                constraints.present.insert(letter);
+                let min = constraints.min_counts.entry(letter).or_default();
+                *min = (*min).max(count);
+            }
+
+            for letter in grays {
+                match counts.get(&letter) {
+                    Some(&count) => {
+                        let max = constraints.max_counts.entry(letter).or_insert(count);
+                        *max = (*max).min(count);
+                    }
+                    None => {
+                        constraints.absent.insert(letter);
+                    }
+                }
+            }
+        }
+
+        constraints
+    }
+
+    /// Returns whether `word` has the known greens in place, no letter where it's forbidden, at
+    /// least as many copies of every present letter as are known, no more than allowed, and none
+    /// of the absent letters.
+    pub fn matches(&self, word: &Word<N>) -> bool {
+        let count = |letter: u8| word.iter().filter(|&&l| l == letter).count();
+
+        self.greens
+            .iter()
+            .zip(word)
+            .all(|(green, letter)| green.is_none_or(|green| green == *letter))
+            && self
+                .forbidden_positions
+                .iter()
+                .zip(word)
+                .all(|(forbidden, letter)| !forbidden.contains(letter))
+            && self
+                .min_counts
+                .iter()
+                .all(|(&letter, &min)| count(letter) >= min)
+            && self
+                .max_counts
+                .iter()
+                .all(|(&letter, &max)| count(letter) <= max)
+            && !word.iter().any(|letter| self.absent.contains(letter))
+    }
+
+    /// Returns whether `GameMode::Hard` lets `word` be guessed after the guesses these constraints
+    /// were gathered from, at the given `strictness`. `Strictness::Strict` is `matches`, while
+    /// `Strictness::Loose` only checks the greens and the fewest copies of every present letter.
+    pub fn matches_with(&self, word: &Word<N>, strictness: Strictness) -> bool {
+        match strictness {
+            Strictness::Strict => self.matches(word),
+            Strictness::Loose => {
+                self.greens
+                    .iter()
+                    .zip(word)
+                    .all(|(green, letter)| green.is_none_or(|green| green == *letter))
+                    && self
+                        .min_counts
+                        .iter()
+                        .all(|(&letter, &min)| word.iter().filter(|&&l| l == letter).count() >= min)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Constraints, Correctness, Guess, RepresentableAsWord, Strictness, Word};
+
+    fn guess(answer: &'static Word, word: &'static Word) -> Guess<'static> {
+        Guess {
+            word,
+            mask: Correctness::check(answer, word),
+        }
+    }
+
+    #[test]
+    fn green_and_gray_copies_give_an_exact_count() {
+        // The last "e" of "geese" is green and the other two are gray: "those" has one "e".
+        let constraints = Constraints::from_guesses(&[guess(b"those", b"geese")]);
+
+        assert_eq!(
+            constraints.greens,
+            [None, None, None, Some(b's'), Some(b'e')]
+        );
+        assert_eq!(constraints.min_counts[&b'e'], 1);
+        assert_eq!(constraints.max_counts[&b'e'], 1);
+        assert!(constraints.present.contains(&b'e'));
+        assert!(!constraints.absent.contains(&b'e'));
+        assert!(constraints.absent.contains(&b'g'));
+
+        assert!(constraints.matches(b"those"));
+        assert!(constraints.matches(b"prose"));
+        assert!(!constraints.matches(b"these"));
+    }
+
+    #[test]
+    fn yellow_and_green_copies_give_a_minimum_count() {
+        // The first "b" of "babes" is yellow and the second green, so "abbey" has two of them.
+        let constraints = Constraints::from_guesses(&[guess(b"abbey", b"babes")]);
+
+        assert_eq!(constraints.min_counts[&b'b'], 2);
+        assert_eq!(constraints.min_counts[&b'a'], 1);
+        assert!(!constraints.max_counts.contains_key(&b'b'));
+        assert!(constraints.absent.contains(&b's'));
+
+        assert!(constraints.matches(b"abbey"));
+        assert!(!constraints.matches(b"ebbed"));
+        assert!(!constraints.matches(b"abbes"));
+    }
+
+    #[test]
+    fn repeated_letters_are_capped_by_their_gray_copies() {
+        // Only the two "o"s in the spots of the "o"s of "robot" are green.
+        let constraints = Constraints::from_guesses(&[guess(b"robot", b"ooooo")]);
+
+        assert_eq!(
+            constraints.greens,
+            [None, Some(b'o'), None, Some(b'o'), None]
+        );
+        assert_eq!(constraints.min_counts[&b'o'], 2);
+        assert_eq!(constraints.max_counts[&b'o'], 2);
+        assert!(constraints.absent.is_empty());
+
+        assert!(constraints.matches(b"robot"));
+        assert!(constraints.matches(b"rotor"));
+        assert!(!constraints.matches(b"boooo"));
+    }
+
+    #[test]
+    fn a_yellow_copy_beside_a_gray_copy_is_not_absent() {
+        // "speed" has two "e"s, and "crane" only one, which is not where either of them is.
+        let constraints = Constraints::from_guesses(&[guess(b"crane", b"speed")]);
+
+        assert_eq!(constraints.greens, [None; crate::WORD_SIZE]);
+        assert_eq!(constraints.min_counts[&b'e'], 1);
+        assert_eq!(constraints.max_counts[&b'e'], 1);
+        assert!(!constraints.absent.contains(&b'e'));
+
+        assert!(constraints.matches(b"crane"));
+        assert!(!constraints.matches(b"genre"));
+    }
+
+    #[test]
+    fn later_guesses_raise_the_minimum() {
+        // "crane" only shows that there is an "e", while "eerie" shows that there are three.
+        let constraints =
+            Constraints::from_guesses(&[guess(b"geese", b"crane"), guess(b"geese", b"eerie")]);
+
+        assert_eq!(constraints.min_counts[&b'e'], 3);
+        assert!(!constraints.max_counts.contains_key(&b'e'));
+        assert!(constraints.matches(b"geese"));
+        assert!(!constraints.matches(b"genre"));
+    }
+
+    #[test]
+    fn yellow_letters_are_forbidden_in_their_spot() {
+        // The "r" of "rusty" is yellow, so "robin" has an "r", just not at the start.
+        let constraints = Constraints::from_guesses(&[guess(b"borne", b"rusty")]);
+
+        assert!(constraints.forbidden_positions[0].contains(&b'r'));
+        assert!(constraints.present.contains(&b'r'));
+
+        assert!(constraints.matches(b"borne"));
+        assert!(!constraints.matches(b"robin"));
+    }
+
+    #[test]
+    fn gray_copies_of_present_letters_are_forbidden_in_their_spot() {
+        // Of the "e"s of "eerie", the first is yellow, the second gray and the last green, so
+        // "there" has exactly two "e"s, and neither of them in the first two spots.
+        let constraints = Constraints::from_guesses(&[guess(b"there", b"eerie")]);
+
+        assert!(constraints.forbidden_positions[0].contains(&b'e'));
+        assert!(constraints.forbidden_positions[1].contains(&b'e'));
+        assert!(!constraints.absent.contains(&b'e'));
+        assert_eq!(constraints.max_counts[&b'e'], 2);
+
+        assert!(constraints.matches(b"there"));
+        assert!(constraints.matches(b"where"));
+        assert!(!constraints.matches(b"eyrie"));
+    }
+
+    #[test]
+    fn every_allowed_word_matches() {
+        let words = Vec::from_iter(
+            include_str!("../dictionary.txt")
+                .split_ascii_whitespace()
+                .step_by(131)
+                .map(|word_str| word_str.as_word()),
+        );
+
+        for &answer in &words {
+            for pair in words.chunks_exact(2).step_by(7) {
+                let past = [guess(answer, pair[0]), guess(answer, pair[1])];
+                let constraints = Constraints::from_guesses(&past);
+
+                // Knowing where letters can't be, the constraints rule out exactly what the masks
+                // do.
+                for &word in &words {
+                    let masks_match = past
+                        .iter()
+                        .all(|guess| Correctness::check(word, guess.word) == guess.mask);
+                    assert_eq!(constraints.matches(word), masks_match);
+                    assert_eq!(
+                        constraints.matches_with(word, Strictness::Strict),
+                        constraints.matches(word)
+                    );
+                    // Loose hard mode lets through everything strict hard mode does, and more.
+                    if constraints.matches(word) {
+                        assert!(constraints.matches_with(word, Strictness::Loose));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn every_mask_of_a_repeated_letter_guess_matches_what_check_gives() {
+        let words = Vec::from_iter(
+            include_str!("../dictionary.txt")
+                .split_ascii_whitespace()
+                .step_by(37)
+                .map(|word_str| word_str.as_word()),
+        );
+
+        // Most masks of "eerie" can't come from any answer, like a yellow "e" after a gray one,
+        // and nothing may match those.
+        for mask in Correctness::all_masks() {
+            let constraints = Constraints::from_guesses(&[Guess::from_mask(b"eerie", mask)]);
+
+            for &word in &words {
+                assert_eq!(
+                    constraints.matches(word),
+                    Correctness::check(word, b"eerie") == mask
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn conflicting_greens_match_nothing() {
+        let past = [
+            Guess::from_mask(b"crane", [Correctness::Correct; 5]),
+            Guess::from_mask(b"slate", [Correctness::Correct; 5]),
+            Guess::from_mask(b"crane", [Correctness::Correct; 5]),
+        ];
+        let constraints = Constraints::from_guesses(&past);
+
+        assert!(!constraints.matches(b"crane"));
+        assert!(!constraints.matches(b"slate"));
+        assert!(!constraints.matches(b"crate"));
+    }
+
+    #[test]
+    fn loose_hard_mode_only_asks_for_the_revealed_letters() {
+        // "crane" shows the green "r" of "tread", and that it has an "a" and an "e" too.
+        let constraints = Constraints::from_guesses(&[guess(b"tread", b"crane")]);
+
+        // "brace" puts the "a" and "e" back where they were yellow, and reuses the gray "c".
+        assert!(constraints.matches_with(b"brace", Strictness::Loose));
+        assert!(!constraints.matches_with(b"brace", Strictness::Strict));
+        // "fried" has no "a", and "raked" moves the "r".
+        assert!(!constraints.matches_with(b"fried", Strictness::Loose));
+        assert!(!constraints.matches_with(b"raked", Strictness::Loose));
+        assert!(constraints.matches_with(b"tread", Strictness::Strict));
+    }
+}
@@ -0,0 +1,73 @@
+//! Maps calendar dates to Wordle's puzzle numbers, so that the answer of a given day can be looked
+//! up. Dates are chrono's, which is why this is behind the `chrono` feature, and the core crate
+//! has no date dependency without it.
+
+use crate::{Word, Wordle};
+use chrono::{NaiveDate, Utc};
+
+/// The day of puzzle 0, whose answer was "cigar".
+const FIRST_PUZZLE: NaiveDate = match NaiveDate::from_ymd_opt(2021, 6, 19) {
+    Some(date) => date,
+    None => panic!("June 19th, 2021 is a date"),
+};
+
+/// The number of the Wordle puzzle of `date`, or `None` before the first puzzle.
+pub fn puzzle_number(date: NaiveDate) -> Option<usize> {
+    usize::try_from((date - FIRST_PUZZLE).num_days()).ok()
+}
+
+/// Today's date in UTC, by the system clock.
+pub fn today() -> NaiveDate {
+    Utc::now().date_naive()
+}
+
+impl<'w, const N: usize> Wordle<'w, N> {
+    /// The answer of the puzzle of `date`, where `answers` lists the answers in the order they're
+    /// used, like `answers.txt` does, or `None` for days before the first puzzle.
+    ///
+    /// Panics if `answers` is empty.
+    pub fn answer_for_date(&self, date: NaiveDate, answers: &[&'w Word<N>]) -> Option<&'w Word<N>> {
+        let index = puzzle_number(date)?;
+
+        Some(self.answer_for_index(index, answers))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod date {
+        use crate::date::{puzzle_number, today};
+        use chrono::NaiveDate;
+
+        #[test]
+        fn puzzles_are_numbered_from_the_first_one() {
+            let date = |year, month, day| NaiveDate::from_ymd_opt(year, month, day).unwrap();
+
+            assert_eq!(puzzle_number(date(2021, 6, 19)), Some(0));
+            assert_eq!(puzzle_number(date(2021, 6, 18)), None);
+            // Puzzle 1000 was on March 15th, 2024.
+            assert_eq!(puzzle_number(date(2024, 3, 15)), Some(1000));
+            assert!(puzzle_number(today()).is_some_and(|number| number >= 1000));
+        }
+    }
+
+    mod answer_for_date {
+        use crate::{RepresentableAsWord, Wordle};
+        use chrono::NaiveDate;
+
+        const GAMES: &str = include_str!("../answers.txt");
+
+        #[test]
+        fn the_first_puzzle_has_the_first_answer() {
+            let answers = Vec::from_iter(GAMES.lines().map(|answer| answer.as_word()));
+            let wordle = Wordle::new(answers.iter().copied());
+
+            let first = NaiveDate::from_ymd_opt(2021, 6, 19).unwrap();
+            assert_eq!(wordle.answer_for_date(first, &answers), Some(b"cigar"));
+            let second = first.succ_opt().unwrap();
+            assert_eq!(wordle.answer_for_date(second, &answers), Some(b"rebut"));
+            let before = NaiveDate::from_ymd_opt(2021, 1, 1).unwrap();
+            assert_eq!(wordle.answer_for_date(before, &answers), None);
+        }
+    }
+}
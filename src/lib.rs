@@ -1,228 +1,4649 @@
 #![feature(slice_as_chunks)]
 
 use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::io::{self, Write};
+use std::path::Path;
+
+pub mod algorithms;
+mod constraints;
+#[cfg(feature = "chrono")]
+pub mod date;
+mod loader;
+#[cfg(feature = "serde")]
+mod serialization;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+pub use constraints::Constraints;
+pub use loader::{DictionaryLoader, LoadWarning, OwnedDictionary};
 
 pub const WORD_SIZE: usize = 5;
 
-/// Wordle only allows six guesses. We allow more to avoid chopping off the score distribution
-/// for stats purposes.
-const TRIES_BEFORE_LOSS: usize = 32;
+/// Wordle only allows six guesses. We allow more to avoid chopping off the score distribution
+/// for stats purposes.
+const TRIES_BEFORE_LOSS: usize = 32;
+
+/// A word of `N` letters. Everything defaults to the `WORD_SIZE` letters of Wordle itself, but
+/// the core game works for variants with other word lengths too.
+pub type Word<const N: usize = WORD_SIZE> = [u8; N];
+
+/// Turns a string literal into a `&'static Word`, like `b"hello"`, except that anything other than
+/// exactly `WORD_SIZE` ASCII characters fails to compile.
+///
+/// ```compile_fail
+/// let word: &roget::Word = roget::word!("abc");
+/// ```
+#[macro_export]
+macro_rules! word {
+    ($word:literal) => {{
+        const WORD: $crate::Word = $crate::word_from_literal($word);
+        &WORD
+    }};
+}
+
+/// What `word!` checks its literal with, which has to be a `const fn` for a bad literal to be a
+/// compile error.
+#[doc(hidden)]
+pub const fn word_from_literal(literal: &str) -> Word {
+    let bytes = literal.as_bytes();
+    assert!(
+        bytes.len() == WORD_SIZE,
+        "A word has to have exactly WORD_SIZE letters"
+    );
+
+    let mut word = [0; WORD_SIZE];
+    let mut i = 0;
+    while i < WORD_SIZE {
+        assert!(bytes[i].is_ascii(), "A word has to be ASCII");
+        word[i] = bytes[i];
+        i += 1;
+    }
+
+    word
+}
+
+/// A Dictionary is a set of words, borrowed for `'w`. Word lists compiled in with `include_str!`
+/// are `'static`, but the words can just as well be borrowed from a buffer read at runtime, as long
+/// as it outlives the games played with them.
+pub type Dictionary<'w, const N: usize = WORD_SIZE> = HashSet<&'w Word<N>>;
+
+/// A DictionaryWithCounts is a set of words alongside an f64 count that gives us an indication of
+/// how frequently this word is encountered in the English language. Counts are kept as f64 since
+/// guessers only ever use them to compute probabilities.
+pub type DictionaryWithCounts<'w, const N: usize = WORD_SIZE> = HashMap<&'w Word<N>, f64>;
+
+/// The counts of a DictionaryWithCounts as probabilities that sum to 1. The reciprocal of the
+/// total count is worked out once up front, so that getting a probability is a multiplication
+/// instead of a sum over every word and a division.
+#[derive(Debug, Clone)]
+pub struct NormalizedCounts<'w, const N: usize = WORD_SIZE> {
+    counts: DictionaryWithCounts<'w, N>,
+    reciprocal: f64,
+}
+
+impl<'w, const N: usize> NormalizedCounts<'w, N> {
+    /// If the counts sum to 0, every word gets a probability of 0.
+    pub fn new(counts: DictionaryWithCounts<'w, N>) -> Self {
+        let total = counts.values().sum::<f64>();
+        let reciprocal = if total > 0.0 { total.recip() } else { 0.0 };
+
+        Self { counts, reciprocal }
+    }
+
+    /// Adds `k` to every count before normalizing, so words with a count of 0 still get a small
+    /// probability instead of none at all, which would contribute `-inf` information.
+    pub fn laplace_smoothing(self, k: f64) -> Self {
+        Self::new(
+            self.counts
+                .into_iter()
+                .map(|(word, count)| (word, count + k))
+                .collect(),
+        )
+    }
+
+    /// The probability of `word`, which is 0 for words that aren't among the counts.
+    pub fn probability(&self, word: &Word<N>) -> f64 {
+        self.counts
+            .get(word)
+            .map_or(0.0, |&count| count * self.reciprocal)
+    }
+
+    /// One over the total count, which turns any of the counts into its probability.
+    pub fn reciprocal(&self) -> f64 {
+        self.reciprocal
+    }
+
+    /// Every word alongside its probability, in no particular order.
+    pub fn probabilities(&self) -> impl Iterator<Item = (&'w Word<N>, f64)> + '_ {
+        self.counts
+            .iter()
+            .map(|(&word, &count)| (word, count * self.reciprocal))
+    }
+}
+
+/// Words are compared byte by byte, so any ASCII character can be part of one: a word list with
+/// digits or apostrophes in it plays just like one of letters. Only non-ASCII input is rejected, by
+/// `try_as_word`, since a character that takes more than a byte would be split across letters.
+pub trait RepresentableAsWord {
+    /// Takes the first `WORD_SIZE` bytes as a word, whatever they are.
+    ///
+    /// Panics if there are fewer than `WORD_SIZE` bytes. Use `try_as_word` to get an error instead,
+    /// e.g. to skip bad lines of a word list.
+    fn as_word(&self) -> &Word;
+
+    /// Like `as_word`, but fails unless this is exactly `WORD_SIZE` ASCII characters.
+    fn try_as_word(&self) -> Result<&Word, WordError>;
+
+    /// Like `as_word`, but with ASCII letters lowercased, so that "HELLO" and "hello" are the same
+    /// word. This has to copy, since `as_word` borrows the bytes as they are.
+    fn as_word_normalized(&self) -> Word {
+        self.as_word().map(|letter| letter.to_ascii_lowercase())
+    }
+
+    /// Like `as_word`, but for the `N`-letter words of Wordle variants.
+    fn as_sized_word<const N: usize>(&self) -> &Word<N>;
+}
+
+impl RepresentableAsWord for str {
+    fn as_word(&self) -> &Word {
+        self.as_bytes().as_word()
+    }
+
+    fn try_as_word(&self) -> Result<&Word, WordError> {
+        self.as_bytes().try_as_word()
+    }
+
+    fn as_sized_word<const N: usize>(&self) -> &Word<N> {
+        self.as_bytes().as_sized_word()
+    }
+}
+
+/// Lets words be read straight out of a byte buffer, e.g. a memory-mapped word list, without going
+/// through UTF-8 validation first.
+impl RepresentableAsWord for [u8] {
+    fn as_word(&self) -> &Word {
+        let (chunks, _): (&[[u8; WORD_SIZE]], &[u8]) = self.as_chunks();
+
+        &chunks[0]
+    }
+
+    fn try_as_word(&self) -> Result<&Word, WordError> {
+        if !self.is_ascii() {
+            return Err(WordError::NotAscii);
+        }
+
+        match self.len() {
+            len if len < WORD_SIZE => Err(WordError::TooShort { len }),
+            len if len > WORD_SIZE => Err(WordError::TooLong { len }),
+            _ => Ok(self.as_word()),
+        }
+    }
+
+    fn as_sized_word<const N: usize>(&self) -> &Word<N> {
+        self.first_chunk()
+            .expect("The word should have at least N letters")
+    }
+}
+
+impl RepresentableAsWord for Word {
+    fn as_word(&self) -> &Word {
+        self
+    }
+
+    fn try_as_word(&self) -> Result<&Word, WordError> {
+        if !self.is_ascii() {
+            return Err(WordError::NotAscii);
+        }
+
+        Ok(self)
+    }
+
+    fn as_sized_word<const N: usize>(&self) -> &Word<N> {
+        self.as_slice().as_sized_word()
+    }
+}
+
+/// The text `word` spells, like "hello" for `b"hello"`, for printing words instead of their
+/// bytes. Words made through `RepresentableAsWord` are ASCII, which is always valid text.
+///
+/// Panics if `word` isn't ASCII, which only a word written as bytes can be.
+pub fn word_str<const N: usize>(word: &Word<N>) -> &str {
+    assert!(word.is_ascii(), "A word has to be ASCII");
+    std::str::from_utf8(word).expect("ASCII is valid UTF-8")
+}
+
+/// Displays a word as the text it spells, and debugs it as a quoted string, where `Word` itself
+/// shows up as a list of bytes. Unlike `word_str`, this never panics: bytes that aren't ASCII show
+/// up as `�`.
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub struct WordDisplay<'a, const N: usize = WORD_SIZE>(pub &'a Word<N>);
+
+impl<const N: usize> fmt::Display for WordDisplay<'_, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(&String::from_utf8_lossy(self.0))
+    }
+}
+
+impl<const N: usize> fmt::Debug for WordDisplay<'_, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", String::from_utf8_lossy(self.0))
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum WordError {
+    /// The word has fewer than `WORD_SIZE` letters; holds the number it has.
+    TooShort { len: usize },
+    /// The word has more than `WORD_SIZE` letters; holds the number it has.
+    TooLong { len: usize },
+    /// The word has characters outside of ASCII, which take more than a byte each.
+    NotAscii,
+}
+
+impl fmt::Display for WordError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooShort { len } => write!(
+                f,
+                "expected a word of {} letters but found {}",
+                WORD_SIZE, len
+            ),
+            Self::TooLong { len } => write!(
+                f,
+                "expected a word of {} letters but found {}",
+                WORD_SIZE, len
+            ),
+            Self::NotAscii => write!(f, "expected a word of ASCII characters"),
+        }
+    }
+}
+
+impl std::error::Error for WordError {}
+
+/// A game of Wordle. Like the real game, it keeps the words that may be guessed apart from the
+/// words that may be the answer, as the latter is a much smaller list. Its words are borrowed for
+/// `'w`, like those of a `Dictionary`, and so are the guesses played in it.
+#[derive(Clone)]
+pub struct Wordle<'w, const N: usize = WORD_SIZE> {
+    allowed_guesses: Dictionary<'w, N>,
+    possible_answers: DictionaryWithCounts<'w, N>,
+    /// How common every word is, for guessers to weigh the possible answers by, if known.
+    counts: Option<DictionaryWithCounts<'w, N>>,
+}
+
+impl<'w, const N: usize> Wordle<'w, N> {
+    /// Makes every word both a possible guess and a possible answer, with all answers equally
+    /// likely.
+    pub fn new<I: IntoIterator<Item = &'w Word<N>>>(iter: I) -> Self {
+        let allowed_guesses = Dictionary::from_iter(iter);
+        let possible_answers = allowed_guesses.iter().map(|&word| (word, 1.0)).collect();
+
+        Self {
+            allowed_guesses,
+            possible_answers,
+            counts: None,
+        }
+    }
+
+    /// Like `new`, but returns `WordleError::EmptyDictionary` instead of a game without any words,
+    /// which no guesser could play.
+    pub fn try_new<I: IntoIterator<Item = &'w Word<N>>>(iter: I) -> Result<Self, WordleError> {
+        let wordle = Self::new(iter);
+        if wordle.allowed_guesses.is_empty() {
+            return Err(WordleError::EmptyDictionary);
+        }
+
+        Ok(wordle)
+    }
+
+    /// Only words in `possible_answers` are meant to be answers, but any word in either list can
+    /// be guessed, since the answers are always allowed guesses too.
+    pub fn with_answers<I: IntoIterator<Item = &'w Word<N>>>(
+        allowed_guesses: I,
+        possible_answers: DictionaryWithCounts<'w, N>,
+    ) -> Self {
+        let mut allowed_guesses = Dictionary::from_iter(allowed_guesses);
+        allowed_guesses.extend(possible_answers.keys());
+
+        Self {
+            allowed_guesses,
+            possible_answers,
+            counts: None,
+        }
+    }
+
+    /// The words that may be guessed.
+    pub fn get_dictionary(&self) -> &Dictionary<'w, N> {
+        &self.allowed_guesses
+    }
+
+    /// The words that may be the answer, alongside their counts.
+    pub fn get_possible_answers(&self) -> &DictionaryWithCounts<'w, N> {
+        &self.possible_answers
+    }
+
+    /// Keeps `counts`, how common every word that has a count is, alongside the game, so that
+    /// guessers can take the dictionary and the counts from the same place, like
+    /// `Unoptimized::from_wordle` does.
+    pub fn with_counts(mut self, counts: DictionaryWithCounts<'w, N>) -> Self {
+        self.counts = Some(counts);
+        self
+    }
+
+    /// The counts given to `with_counts`, or the ones `WordleBuilder` read, if any.
+    pub fn get_counts(&self) -> Option<&DictionaryWithCounts<'w, N>> {
+        self.counts.as_ref()
+    }
+
+    /// The possible answers, weighed by the counts of `get_counts` wherever those have them, which
+    /// is what guessers that weigh answers by how common they are start from.
+    pub fn counted_answers(&self) -> DictionaryWithCounts<'w, N> {
+        self.possible_answers
+            .iter()
+            .map(|(&word, &count)| {
+                let count = self
+                    .counts
+                    .as_ref()
+                    .and_then(|counts| counts.get(word))
+                    .copied()
+                    .unwrap_or(count);
+
+                (word, count)
+            })
+            .collect()
+    }
+
+    /// The answer of puzzle number `index`, where `answers` lists the answers in the order they're
+    /// used, like `answers.txt` does for Wordle. The list starts over once every answer was used.
+    ///
+    /// Panics if `answers` is empty.
+    pub fn answer_for_index(&self, index: usize, answers: &[&'w Word<N>]) -> &'w Word<N> {
+        assert!(!answers.is_empty(), "There has to be at least one answer");
+        answers[index % answers.len()]
+    }
+
+    /// A function play that takes a generic G that implements the trait Guesser.
+    ///
+    /// Panics if the guesser makes a guess that is not in the dictionary; use `try_play` to get a
+    /// `PlayError` instead.
+    pub fn play<G: Guesser<'w, N>>(&self, answer: &'w Word<N>, guesser: G) -> Option<usize> {
+        self.try_play(answer, guesser)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Like `play`, but returns a `PlayError` instead of panicking when the guesser misbehaves.
+    pub fn try_play<G: Guesser<'w, N>>(
+        &self,
+        answer: &'w Word<N>,
+        guesser: G,
+    ) -> Result<Option<usize>, PlayError<N>> {
+        self.try_play_recorded(answer, guesser)
+            .map(|record| record.outcome)
+    }
+
+    /// Like `play`, but keeps every guess that was made in the returned `GameRecord`.
+    pub fn play_recorded<G: Guesser<'w, N>>(
+        &self,
+        answer: &'w Word<N>,
+        guesser: G,
+    ) -> GameRecord<'w, N> {
+        self.try_play_recorded(answer, guesser)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub fn try_play_recorded<G: Guesser<'w, N>>(
+        &self,
+        answer: &'w Word<N>,
+        mut guesser: G,
+    ) -> Result<GameRecord<'w, N>, PlayError<N>> {
+        self.play_game(
+            answer,
+            &mut guesser,
+            GameMode::Normal,
+            TRIES_BEFORE_LOSS,
+            &mut |_, _| {},
+        )
+    }
+
+    /// Like `try_play`, but enforces the rules of the given `GameMode` on every guess.
+    pub fn play_with_mode<G: Guesser<'w, N>>(
+        &self,
+        answer: &'w Word<N>,
+        mut guesser: G,
+        mode: GameMode,
+    ) -> Result<Option<usize>, PlayError<N>> {
+        self.play_game(
+            answer,
+            &mut guesser,
+            mode,
+            TRIES_BEFORE_LOSS,
+            &mut |_, _| {},
+        )
+        .map(|record| record.outcome)
+    }
+
+    /// Like `play`, but the game is lost after `max_tries` guesses instead of the default limit.
+    /// Use `usize::MAX` to keep playing until the answer is guessed.
+    pub fn play_with_limit<G: Guesser<'w, N>>(
+        &self,
+        answer: &'w Word<N>,
+        mut guesser: G,
+        max_tries: usize,
+    ) -> Option<usize> {
+        self.play_game(
+            answer,
+            &mut guesser,
+            GameMode::Normal,
+            max_tries,
+            &mut |_, _| {},
+        )
+        .unwrap_or_else(|error| panic!("{}", error))
+        .outcome
+    }
+
+    /// Like `play`, but calls `observer` with every guess and its 1-based attempt number as soon
+    /// as its mask is known, so a game can be logged, drawn or checked as it goes without the
+    /// library printing anything itself.
+    pub fn play_with_observer<G: Guesser<'w, N>, O: FnMut(&Guess<'w, N>, usize)>(
+        &self,
+        answer: &'w Word<N>,
+        mut guesser: G,
+        mut observer: O,
+    ) -> Option<usize> {
+        self.play_game(
+            answer,
+            &mut guesser,
+            GameMode::Normal,
+            TRIES_BEFORE_LOSS,
+            &mut observer,
+        )
+        .unwrap_or_else(|error| panic!("{}", error))
+        .outcome
+    }
+
+    /// Like `play`, but hands the guesser back once the game is over, so that what it kept track
+    /// of along the way, like the timings of `Unoptimized::with_profiling`, can be looked at.
+    pub fn play_returning<G: Guesser<'w, N>>(
+        &self,
+        answer: &'w Word<N>,
+        mut guesser: G,
+    ) -> (Option<usize>, G) {
+        let outcome = self
+            .play_game(
+                answer,
+                &mut guesser,
+                GameMode::Normal,
+                TRIES_BEFORE_LOSS,
+                &mut |_, _| {},
+            )
+            .unwrap_or_else(|error| panic!("{}", error))
+            .outcome;
+
+        (outcome, guesser)
+    }
+
+    /// Plays a game of Absurdle, where there is no answer up front. After every guess, the
+    /// possible answers are split up by the mask they would give, and the mask shared by the most
+    /// of them is the one that gets played, so the game is only won by guessing the last word
+    /// that's left. Between equally large groups of answers, a mask that isn't the winning one
+    /// comes first, then the one with the most grays and yellows from the left.
+    ///
+    /// Like `play`, this returns how many guesses it took, or `None` if it took too many, and
+    /// panics on a guess that is not in the dictionary. It also panics if the game has no
+    /// possible answers. Use `try_play_adversarial` to get a `PlayError` instead.
+    pub fn play_adversarial<G: Guesser<'w, N>>(&self, guesser: G) -> Option<usize> {
+        self.try_play_adversarial(guesser)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Like `play_adversarial`, but returns a `PlayError` instead of panicking when the guesser
+    /// misbehaves, or when there are no possible answers to play with.
+    pub fn try_play_adversarial<G: Guesser<'w, N>>(
+        &self,
+        mut guesser: G,
+    ) -> Result<Option<usize>, PlayError<N>> {
+        if self.possible_answers.is_empty() {
+            return Err(PlayError::NoPossibleAnswers { attempt: 1 });
+        }
+
+        let mut remaining = Vec::from_iter(self.possible_answers.keys().copied());
+        remaining.sort_unstable();
+        let mut past_guesses: Vec<Guess<'w, N>> = Vec::new();
+        let won = [Correctness::Correct; N];
+
+        for attempt_index in 1..=TRIES_BEFORE_LOSS {
+            let context = GameContext {
+                attempt: attempt_index,
+                remaining_hint: Some(remaining.len()),
+            };
+            let guessed_word = guesser.guess_with_context(&past_guesses[..], &context);
+            if !self.allowed_guesses.contains(&guessed_word) {
+                return Err(PlayError::IllegalGuess {
+                    attempt: attempt_index,
+                    word: *guessed_word,
+                });
+            }
+
+            let mut buckets: HashMap<[Correctness; N], Vec<&'w Word<N>>> = HashMap::new();
+            for &answer in &remaining {
+                buckets
+                    .entry(Correctness::check(answer, guessed_word))
+                    .or_default()
+                    .push(answer);
+            }
+            let (mask, bucket) = buckets
+                .into_iter()
+                .max_by(|(a_mask, a), (b_mask, b)| {
+                    a.len()
+                        .cmp(&b.len())
+                        .then((*a_mask != won).cmp(&(*b_mask != won)))
+                        .then(a_mask.cmp(b_mask))
+                })
+                .expect("There is at least one possible answer left");
+
+            if mask == won {
+                return Ok(Some(attempt_index));
+            }
+            remaining = bucket;
+            past_guesses.push(Guess::from_mask(guessed_word, mask));
+        }
+
+        Ok(None)
+    }
+
+    /// Plays one game per answer, each with a fresh guesser from `guesser_factory`, and sums up how
+    /// many guesses the games took.
+    ///
+    /// Panics if a guesser makes a guess that is not in the dictionary, like `play`.
+    pub fn evaluate<G, F>(
+        &self,
+        answers: impl IntoIterator<Item = &'w Word<N>>,
+        mut guesser_factory: F,
+    ) -> ScoreDistribution
+    where
+        G: Guesser<'w, N>,
+        F: FnMut() -> G,
+    {
+        answers
+            .into_iter()
+            .map(|answer| self.play(answer, guesser_factory()))
+            .collect()
+    }
+
+    /// The `n` answers that took the most guesses, hardest first, alongside how many guesses they
+    /// took, or `None` for the games that were lost, which rank above every game that was won.
+    /// Answers that took just as many guesses come alphabetically. Each game is played with a
+    /// fresh guesser from `make`, like in `evaluate`.
+    ///
+    /// Panics if a guesser makes a guess that is not in the dictionary, like `play`.
+    pub fn hardest_answers<G: Guesser<'w, N>>(
+        &self,
+        answers: impl IntoIterator<Item = &'w Word<N>>,
+        make: impl Fn() -> G,
+        n: usize,
+    ) -> Vec<(&'w Word<N>, Option<usize>)> {
+        let mut games = Vec::from_iter(
+            answers
+                .into_iter()
+                .map(|answer| (answer, self.play(answer, make()))),
+        );
+        games.sort_unstable_by(|(a, a_guesses), (b, b_guesses)| {
+            // A lost game took more guesses than any game that was won.
+            let key = |guesses: &Option<usize>| guesses.unwrap_or(usize::MAX);
+            key(b_guesses).cmp(&key(a_guesses)).then(a.cmp(b))
+        });
+        games.truncate(n);
+
+        games
+    }
+
+    /// Like `evaluate`, but also writes one row per game to `writer` as soon as the game is over,
+    /// so that the results of a long run never have to be held in memory. Each row has the name of
+    /// the guesser, the answer, the number of guesses or nothing if the game was lost, and with
+    /// `with_guesses`, the guesses that were made.
+    pub fn evaluate_to_writer<G, F, W>(
+        &self,
+        answers: impl IntoIterator<Item = &'w Word<N>>,
+        mut guesser_factory: F,
+        mut writer: W,
+        format: OutputFormat,
+        with_guesses: bool,
+    ) -> io::Result<ScoreDistribution>
+    where
+        G: Guesser<'w, N>,
+        F: FnMut() -> G,
+        W: Write,
+    {
+        let mut distribution = ScoreDistribution::default();
+
+        match format {
+            OutputFormat::Csv if with_guesses => {
+                writeln!(writer, "guesser,answer,guesses,sequence")?
+            }
+            OutputFormat::Csv => writeln!(writer, "guesser,answer,guesses")?,
+            OutputFormat::Json => write!(writer, "[")?,
+        }
+
+        for (i, answer) in answers.into_iter().enumerate() {
+            let guesser = guesser_factory();
+            let name = guesser.name();
+            let record = self.play_recorded(answer, guesser);
+            distribution.record(record.outcome);
+
+            if format == OutputFormat::Json && i > 0 {
+                write!(writer, ",")?;
+            }
+            record.write_row(&mut writer, name, format, with_guesses)?;
+        }
+
+        if format == OutputFormat::Json {
+            writeln!(writer, "\n]")?;
+        }
+        writer.flush()?;
+
+        Ok(distribution)
+    }
+
+    fn play_game<G: Guesser<'w, N>>(
+        &self,
+        answer: &'w Word<N>,
+        guesser: &mut G,
+        mode: GameMode,
+        max_tries: usize,
+        observer: &mut impl FnMut(&Guess<'w, N>, usize),
+    ) -> Result<GameRecord<'w, N>, PlayError<N>> {
+        // An answer that can't be guessed can't be found either, and one that isn't a possible
+        // answer gets ruled out along the way, leaving guessers that narrow down the possible
+        // answers with nothing to guess from.
+        if !self.allowed_guesses.contains(answer) || !self.possible_answers.contains_key(answer) {
+            return Err(PlayError::UnknownAnswer { word: *answer });
+        }
+
+        // play up to `max_tries` rounds where it invokes the guesser each round
+        let mut past_guesses: Vec<Guess<'w, N>> = Vec::new();
+        let mut expected_information = Vec::new();
+        let mut remaining = Vec::from_iter(self.possible_answers.keys().copied());
+
+        for attempt_index in 1..=max_tries {
+            if let Some(last) = past_guesses.last() {
+                let constraints = Constraints::from_guesses(std::slice::from_ref(last));
+                remaining.retain(|word| constraints.matches(word));
+            }
+            // The answer gives every guess its mask, so `remaining` is never empty here.
+            let context = GameContext {
+                attempt: attempt_index,
+                remaining_hint: Some(remaining.len()),
+            };
+
+            let guessed_word = guesser.guess_with_context(&past_guesses[..], &context);
+            if !self.allowed_guesses.contains(&guessed_word) {
+                return Err(PlayError::IllegalGuess {
+                    attempt: attempt_index,
+                    word: *guessed_word,
+                });
+            }
+
+            if let GameMode::Hard(strictness) = mode {
+                if !Constraints::from_guesses(&past_guesses).matches_with(guessed_word, strictness)
+                {
+                    return Err(PlayError::HardModeViolation {
+                        attempt: attempt_index,
+                        word: *guessed_word,
+                    });
+                }
+            }
+
+            let guess = Guess::new(answer, guessed_word);
+            observer(&guess, attempt_index);
+            past_guesses.push(guess);
+            expected_information.push(guesser.last_expected_information());
+
+            if guessed_word.eq(answer) {
+                return Ok(GameRecord {
+                    answer,
+                    guesses: past_guesses,
+                    outcome: Some(attempt_index),
+                    expected_information,
+                });
+            }
+        }
+
+        Ok(GameRecord {
+            answer,
+            guesses: past_guesses,
+            outcome: None,
+            expected_information,
+        })
+    }
+}
+
+impl Wordle<'static> {
+    /// Starts building a game from a list of words and their counts in the format of
+    /// `joined.txt`, so that the game and the counts guessers start from come from the same list.
+    pub fn builder(counts: &'static str) -> WordleBuilder {
+        WordleBuilder {
+            counts,
+            dictionary: None,
+            answers: None,
+        }
+    }
+
+    /// Reads a game from a word list on disk, in the format of `dictionary.txt`, where every word
+    /// can be guessed and can be the answer. The words are leaked to live for the rest of the
+    /// program, see `OwnedDictionary`.
+    pub fn from_file(path: impl AsRef<Path>) -> io::Result<(Self, Vec<LoadWarning>)> {
+        let (dictionary, warnings) = OwnedDictionary::from_file(path)?.wordlist();
+
+        Ok((Self::new(dictionary), warnings))
+    }
+
+    /// A game from a list of one word per line, in the format of `dictionary.txt`, where every
+    /// word can be guessed and can be the answer. Lines `DictionaryLoader::from_wordlist` would
+    /// warn about are skipped without a word, so use that instead to find out about them.
+    ///
+    /// Unlike splitting `list` on whitespace, every line is one word, so a line with several
+    /// words on it is skipped as too long, and so are blank lines and lines starting with `#`.
+    pub fn from_str_list(list: &'static str) -> Self {
+        let (dictionary, _) = DictionaryLoader::from_wordlist(list);
+
+        Self::new(dictionary)
+    }
+}
+
+impl<'w> Wordle<'w> {
+    /// The `top_n` best openers of the dictionary when the answer's counts are `counts`, from the
+    /// best down, alongside how many bits of information each gives on average. This is the
+    /// ranking `Unoptimized` picks its opener from.
+    pub fn rank_openers(
+        &self,
+        counts: &DictionaryWithCounts<'w>,
+        top_n: usize,
+    ) -> Vec<(&Word, f64)> {
+        algorithms::Unoptimized::new(self.get_dictionary(), counts.clone())
+            .ranked_guesses(&[])
+            .into_iter()
+            .take(top_n)
+            .map(|candidate| (candidate.word, candidate.expected_information))
+            .collect()
+    }
+}
+
+/// A game of Quordle, Octordle and the like, where every guess is played on several boards at
+/// once, each with an answer of its own, until every board is solved.
+#[derive(Clone)]
+pub struct MultiWordle<'w, const N: usize = WORD_SIZE> {
+    wordle: Wordle<'w, N>,
+}
+
+impl<'w, const N: usize> MultiWordle<'w, N> {
+    /// Plays with the words of `wordle` on every board.
+    pub fn new(wordle: Wordle<'w, N>) -> Self {
+        Self { wordle }
+    }
+
+    /// Plays one board per answer, and returns how many guesses it took to solve all of them, or
+    /// `None` if it took too many. A board that's solved gets no more guesses.
+    ///
+    /// Panics if an answer or a guess is not in the dictionary, like `Wordle::play`. Use
+    /// `try_play_multi` to get a `PlayError` instead.
+    pub fn play_multi<G: MultiGuesser<'w, N>>(
+        &self,
+        answers: &[&'w Word<N>],
+        guesser: G,
+    ) -> Option<usize> {
+        self.try_play_multi(answers, guesser)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Like `play_multi`, but returns a `PlayError` instead of panicking.
+    pub fn try_play_multi<G: MultiGuesser<'w, N>>(
+        &self,
+        answers: &[&'w Word<N>],
+        mut guesser: G,
+    ) -> Result<Option<usize>, PlayError<N>> {
+        if let Some(answer) = answers
+            .iter()
+            .find(|answer| !self.wordle.allowed_guesses.contains(**answer))
+        {
+            return Err(PlayError::UnknownAnswer { word: **answer });
+        }
+
+        let mut boards = Vec::from_iter(answers.iter().map(|_| Vec::new()));
+        let mut solved = vec![false; answers.len()];
+
+        for attempt_index in 1..=TRIES_BEFORE_LOSS {
+            let guessed_word = guesser.guess(&boards);
+            if !self.wordle.allowed_guesses.contains(&guessed_word) {
+                return Err(PlayError::IllegalGuess {
+                    attempt: attempt_index,
+                    word: *guessed_word,
+                });
+            }
+
+            for ((board, solved), &answer) in boards.iter_mut().zip(&mut solved).zip(answers) {
+                if !*solved {
+                    board.push(Guess::new(answer, guessed_word));
+                    *solved = guessed_word == answer;
+                }
+            }
+
+            if solved.iter().all(|&solved| solved) {
+                return Ok(Some(attempt_index));
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+pub struct WordleBuilder {
+    counts: &'static str,
+    dictionary: Option<&'static str>,
+    answers: Option<&'static str>,
+}
+
+impl WordleBuilder {
+    /// Checks that this list of allowed guesses, one word per line, has exactly the words that
+    /// have counts. Without it, the counted words are the allowed guesses.
+    pub fn dictionary(mut self, dictionary: &'static str) -> Self {
+        self.dictionary = Some(dictionary);
+        self
+    }
+
+    /// Only lets the words of this list, one word per line, be the answer. Every one of them
+    /// needs a count. Without it, every counted word can be the answer.
+    pub fn answers(mut self, answers: &'static str) -> Self {
+        self.answers = Some(answers);
+        self
+    }
+
+    /// Builds the game, alongside the counts of its possible answers for guessers to start from.
+    /// The game keeps the counts of every word too, see `Wordle::get_counts`.
+    ///
+    /// Fails on the first line of any list that `DictionaryLoader` would skip, or on the
+    /// alphabetically first word that one list has and another doesn't.
+    pub fn build(
+        self,
+    ) -> Result<(Wordle<'static>, DictionaryWithCounts<'static>), WordleBuildError> {
+        let (counts, warnings) = DictionaryLoader::from_counts(self.counts);
+        first_warning(warnings)?;
+
+        let dictionary = match self.dictionary {
+            Some(dictionary) => {
+                let (dictionary, warnings) = DictionaryLoader::from_wordlist(dictionary);
+                first_warning(warnings)?;
+
+                if let Some(word) = first_missing(dictionary.iter(), &counts) {
+                    return Err(WordleBuildError::MissingCount(word));
+                }
+                if let Some(&word) = counts
+                    .keys()
+                    .filter(|word| !dictionary.contains(*word))
+                    .min()
+                {
+                    return Err(WordleBuildError::NotInDictionary(word));
+                }
+
+                dictionary
+            }
+            None => counts.keys().copied().collect(),
+        };
+
+        let possible_answers: DictionaryWithCounts = match self.answers {
+            Some(answers) => {
+                let (answers, warnings) = DictionaryLoader::from_wordlist(answers);
+                first_warning(warnings)?;
+
+                if let Some(word) = first_missing(answers.iter(), &counts) {
+                    return Err(WordleBuildError::MissingCount(word));
+                }
+
+                answers
+                    .into_iter()
+                    .map(|word| (word, counts[word]))
+                    .collect()
+            }
+            None => counts.clone(),
+        };
+
+        Ok((
+            Wordle::with_answers(dictionary, possible_answers.clone()).with_counts(counts),
+            possible_answers,
+        ))
+    }
+}
+
+fn first_warning(warnings: Vec<LoadWarning>) -> Result<(), WordleBuildError> {
+    match warnings.into_iter().next() {
+        Some(warning) => Err(WordleBuildError::InvalidLine(warning)),
+        None => Ok(()),
+    }
+}
+
+/// The alphabetically first of `words` that has no count.
+fn first_missing<'a>(
+    words: impl Iterator<Item = &'a &'static Word>,
+    counts: &DictionaryWithCounts,
+) -> Option<&'static Word> {
+    words
+        .copied()
+        .filter(|word| !counts.contains_key(word))
+        .min()
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum WordleError {
+    /// There are no words to guess or to be the answer.
+    EmptyDictionary,
+}
+
+impl fmt::Display for WordleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EmptyDictionary => write!(f, "the dictionary has no words"),
+        }
+    }
+}
+
+impl std::error::Error for WordleError {}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum WordleBuildError {
+    /// One of the lists has a line that isn't a valid entry.
+    InvalidLine(LoadWarning),
+    /// The word is an allowed guess or a possible answer, but has no count.
+    MissingCount(&'static Word),
+    /// The word has a count, but is not in the dictionary of allowed guesses.
+    NotInDictionary(&'static Word),
+}
+
+impl fmt::Display for WordleBuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidLine(warning) => write!(f, "{}", warning),
+            Self::MissingCount(word) => {
+                write!(f, "{} has no count", WordDisplay(word))
+            }
+            Self::NotInDictionary(word) => write!(
+                f,
+                "{} has a count but is not in the dictionary",
+                WordDisplay(word)
+            ),
+        }
+    }
+}
+
+impl std::error::Error for WordleBuildError {}
+
+/// Everything that happened in one game of Wordle.
+pub struct GameRecord<'w, const N: usize = WORD_SIZE> {
+    pub answer: &'w Word<N>,
+    /// Every guess made, in order, including the final correct one if the game was won.
+    pub guesses: Vec<Guess<'w, N>>,
+    /// The number of guesses it took to win, or `None` if the game was lost.
+    pub outcome: Option<usize>,
+    /// What the guesser expected every guess to tell, in the order of `guesses`, see
+    /// `Guesser::last_expected_information`.
+    pub expected_information: Vec<Option<f64>>,
+}
+
+/// Shows the answer and the guesses as the text they spell, like `WordDisplay`.
+impl<const N: usize> fmt::Debug for GameRecord<'_, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GameRecord")
+            .field("answer", &WordDisplay(self.answer))
+            .field("guesses", &self.guesses)
+            .field("outcome", &self.outcome)
+            .field("expected_information", &self.expected_information)
+            .finish()
+    }
+}
+
+impl<'w, const N: usize> GameRecord<'w, N> {
+    /// Renders the game as the grid of emoji rows that Wordle shares, one row per guess.
+    pub fn to_emoji(&self, dark_mode: bool) -> String {
+        self.render(RenderStyle::Emoji, dark_mode)
+    }
+
+    /// Like `to_emoji`, but with the masks drawn in `style`, see `Correctness::render`.
+    pub fn render(&self, style: RenderStyle, dark_mode: bool) -> String {
+        self.guesses
+            .iter()
+            .map(|guess| Correctness::render(&guess.mask, style, dark_mode))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// How much the guesser expected every guess to tell about the answer, and how much it did, in
+    /// the order the guesses were made. `possible_answers` are the answers the game started out
+    /// with, which every guess narrows down, and every one of them is counted the same, like in
+    /// `realized_information`.
+    ///
+    /// A guess that told less than expected was unlucky, while one that was expected to tell
+    /// little was simply a poor guess.
+    pub fn information(
+        &self,
+        possible_answers: &DictionaryWithCounts<'w, N>,
+    ) -> Vec<GuessInformation> {
+        let mut remaining = Vec::from_iter(possible_answers.keys().copied());
+
+        self.guesses
+            .iter()
+            .zip(&self.expected_information)
+            .map(|(guess, &expected)| {
+                let before = remaining.len();
+                let constraints = Constraints::from_guesses(std::slice::from_ref(guess));
+                remaining.retain(|answer| constraints.matches(answer));
+
+                GuessInformation {
+                    expected,
+                    realized: realized_information(before, remaining.len()),
+                }
+            })
+            .collect()
+    }
+}
+
+/// How many bits of information one guess of a game was worth, see `GameRecord::information`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct GuessInformation {
+    /// The information the guesser expected the guess to give before its mask was known, if it
+    /// said, see `Guesser::last_expected_information`.
+    pub expected: Option<f64>,
+    /// The information its mask actually gave, see `realized_information`.
+    pub realized: f64,
+}
+
+/// The information a mask gave by narrowing `before` possible answers down to `after`, which is
+/// `-log2(after / before)`. Every bit halves the possible answers, so going from 8 to 2 is 2 bits.
+/// A mask no answer fits gives infinite information.
+pub fn realized_information(before: usize, after: usize) -> f64 {
+    // Without any answers to begin with, `0 / 0` would make this NaN instead.
+    if after == 0 {
+        return f64::INFINITY;
+    }
+
+    -(after as f64 / before as f64).log2()
+}
+
+impl<'w, const N: usize> GameRecord<'w, N> {
+    fn write_row<W: Write>(
+        &self,
+        writer: &mut W,
+        guesser: &str,
+        format: OutputFormat,
+        with_guesses: bool,
+    ) -> io::Result<()> {
+        let answer = WordDisplay(self.answer).to_string();
+        let words = self
+            .guesses
+            .iter()
+            .map(|guess| WordDisplay(guess.word).to_string());
+
+        match format {
+            OutputFormat::Csv => {
+                write!(writer, "{},{},", csv_field(guesser), csv_field(&answer))?;
+                if let Some(outcome) = self.outcome {
+                    write!(writer, "{}", outcome)?;
+                }
+                if with_guesses {
+                    let words = words.collect::<Vec<_>>().join(" ");
+                    write!(writer, ",{}", csv_field(&words))?;
+                }
+                writeln!(writer)
+            }
+            OutputFormat::Json => {
+                write!(
+                    writer,
+                    "\n{{\"guesser\":{},\"answer\":{},\"guesses\":",
+                    json_string(guesser),
+                    json_string(&answer)
+                )?;
+                match self.outcome {
+                    Some(outcome) => write!(writer, "{}", outcome)?,
+                    None => write!(writer, "null")?,
+                }
+                if with_guesses {
+                    let words = words.map(|word| json_string(&word));
+                    write!(
+                        writer,
+                        ",\"sequence\":[{}]",
+                        words.collect::<Vec<_>>().join(",")
+                    )?;
+                }
+                write!(writer, "}}")
+            }
+        }
+    }
+}
+
+/// Quotes `field` for a CSV row if it has a comma, a quote or a line break in it, doubling the
+/// quotes inside, and leaves it as it is otherwise.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        String::from(field)
+    }
+}
+
+/// Writes `string` as a quoted JSON string, escaping quotes, backslashes and control characters.
+fn json_string(string: &str) -> String {
+    let mut escaped = String::from("\"");
+    for c in string.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+
+    escaped
+}
+
+/// With at most this many possible answers left, `describe_state` lists them.
+const DESCRIBED_ANSWERS: usize = 10;
+
+/// Describes how many possible answers are left, e.g. to show how close a game is to being
+/// solved. When there are few enough, they're listed from the most to the least common, like
+/// "2 possible answers left: which, moved". `remaining` is expected to be narrowed down already,
+/// by keeping the possible answers that match the `Constraints` of the guesses so far.
+pub fn describe_state<const N: usize>(remaining: &DictionaryWithCounts<'_, N>) -> String {
+    let description = match remaining.len() {
+        1 => String::from("1 possible answer left"),
+        len => format!("{} possible answers left", len),
+    };
+    if remaining.is_empty() || remaining.len() > DESCRIBED_ANSWERS {
+        return description;
+    }
+
+    let mut words = Vec::from_iter(remaining);
+    words.sort_unstable_by(|(a, a_count), (b, b_count)| b_count.total_cmp(a_count).then(a.cmp(b)));
+    let words = Vec::from_iter(words.iter().map(|(word, _)| WordDisplay(word).to_string()));
+
+    format!("{}: {}", description, words.join(", "))
+}
+
+/// How masks are drawn by `Correctness::render`, `GameRecord::render` and `render_board_styled`.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Hash)]
+pub enum RenderStyle {
+    /// Green for correct letters and yellow for misplaced ones, like Wordle shares by default.
+    #[default]
+    Emoji,
+    /// The letters of `Correctness::as_letter`, like "CMWWC", for where color can't be shown.
+    Ascii,
+    /// Orange for correct letters and blue for misplaced ones, like Wordle's high contrast theme,
+    /// which tells them apart without relying on red and green.
+    ColorblindEmoji,
+}
+
+/// Draws `guesses` as a Wordle board, one guess per line, with every letter on a green, yellow or
+/// gray background by its mask. When the `NO_COLOR` environment variable is set, every guess is
+/// written the way `Guess` displays instead, like "crane WMCWW".
+pub fn render_board<const N: usize>(guesses: &[Guess<'_, N>]) -> String {
+    let style = match std::env::var_os("NO_COLOR") {
+        Some(_) => RenderStyle::Ascii,
+        None => RenderStyle::Emoji,
+    };
+
+    render_board_styled(guesses, style)
+}
+
+/// Like `render_board`, but in `style` whatever the environment says. `RenderStyle::Ascii` writes
+/// every guess the way `Guess` displays, and `RenderStyle::ColorblindEmoji` puts the letters on an
+/// orange, blue or gray background instead.
+pub fn render_board_styled<const N: usize>(guesses: &[Guess<'_, N>], style: RenderStyle) -> String {
+    let mut board = String::new();
+    for guess in guesses {
+        if style == RenderStyle::Ascii {
+            board.push_str(&format!("{}\n", guess));
+            continue;
+        }
+
+        for (&letter, correctness) in guess.word.iter().zip(guess.mask) {
+            let background = match (correctness, style) {
+                (Correctness::Correct, RenderStyle::ColorblindEmoji) => "48;5;208",
+                (Correctness::Misplaced, RenderStyle::ColorblindEmoji) => "44",
+                (Correctness::Correct, _) => "42",
+                (Correctness::Misplaced, _) => "43",
+                (Correctness::Wrong, _) => "100",
+            };
+            board.push_str(&format!(
+                "\x1b[30;{}m {} \x1b[0m",
+                background,
+                letter.to_ascii_uppercase() as char
+            ));
+        }
+        board.push('\n');
+    }
+
+    board
+}
+
+/// Reads the transcript of a game, with one guess per line written as its word and then its mask,
+/// like "crane 🟩⬛⬛⬛🟨" or "crane CWWWM", into the guesses it lists. Masks are read by
+/// `Correctness::parse_mask`, so they can be emoji or letters, and blank lines are skipped.
+///
+/// The guesses borrow their words from `transcript`.
+pub fn parse_transcript(transcript: &str) -> Result<Vec<Guess<'_>>, TranscriptError> {
+    transcript
+        .lines()
+        .enumerate()
+        .map(|(i, text)| (i + 1, text.trim()))
+        .filter(|(_, text)| !text.is_empty())
+        .map(|(line, text)| {
+            // A line without a mask reads as an empty mask, which has the wrong length.
+            let (word, mask) = text.split_once(char::is_whitespace).unwrap_or((text, ""));
+            let word = word
+                .try_as_word()
+                .map_err(|error| TranscriptError::Word { line, error })?;
+            let mask = Correctness::parse_mask(mask)
+                .map_err(|error| TranscriptError::Mask { line, error })?;
+
+            Ok(Guess::from_mask(word, mask))
+        })
+        .collect()
+}
+
+/// Why `parse_transcript` couldn't read a transcript, with the line it failed on, counting from 1.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum TranscriptError {
+    /// The word on the line isn't a valid word.
+    Word { line: usize, error: WordError },
+    /// The mask on the line is missing or isn't a valid mask.
+    Mask { line: usize, error: ParseMaskError },
+}
+
+impl fmt::Display for TranscriptError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Word { line, error } => write!(f, "line {}: {}", line, error),
+            Self::Mask { line, error } => write!(f, "line {}: {}", line, error),
+        }
+    }
+}
+
+impl std::error::Error for TranscriptError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Word { error, .. } => Some(error),
+            Self::Mask { error, .. } => Some(error),
+        }
+    }
+}
+
+/// The letters words are spelled with, mapped to the indices `0..size()`, for the tables that
+/// have an entry per letter, like `letter_status_in` and `positional_frequencies_in`. Words are
+/// checked byte by byte whatever the alphabet, so this only matters to analysis and display.
+pub trait Alphabet {
+    /// How many letters there are, which is one more than the greatest index.
+    fn size(&self) -> usize;
+
+    /// The index of `letter`, or `None` if it isn't a letter of this alphabet.
+    fn index(&self, letter: u8) -> Option<usize>;
+}
+
+/// The letters from 'a' to 'z', which is what the word lists of the crate are spelled with.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Hash)]
+pub struct AsciiLowercase;
+
+impl Alphabet for AsciiLowercase {
+    fn size(&self) -> usize {
+        26
+    }
+
+    fn index(&self, letter: u8) -> Option<usize> {
+        letter
+            .is_ascii_lowercase()
+            .then(|| usize::from(letter - b'a'))
+    }
+}
+
+/// What past guesses tell about a letter, the way the keyboard of Wordle colors it. States are
+/// ordered from the least to the most that's known, so a letter takes the greatest state any of
+/// its guesses gave it.
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum LetterState {
+    /// The letter wasn't guessed yet.
+    Unknown,
+    /// The letter was guessed, and isn't in the answer.
+    Absent,
+    /// The letter is in the answer, but wasn't guessed in its spot yet.
+    Present,
+    /// The letter was guessed in its spot at least once.
+    Placed,
+}
+
+/// The state of every letter from 'a' to 'z' after the guesses in `past`. A letter that was gray in
+/// one guess and green in another is `Placed`, just like a gray copy of a letter that's yellow or
+/// green elsewhere in the same guess doesn't make it `Absent`.
+pub fn letter_status<const N: usize>(past: &[Guess<'_, N>]) -> [LetterState; 26] {
+    letter_status_in(past, &AsciiLowercase)
+        .try_into()
+        .expect("There is a state for every letter of the alphabet")
+}
+
+/// Like `letter_status`, but for the letters of `alphabet`, by their index. Bytes that aren't in
+/// the alphabet are left out.
+pub fn letter_status_in<const N: usize>(
+    past: &[Guess<'_, N>],
+    alphabet: &impl Alphabet,
+) -> Vec<LetterState> {
+    let mut states = vec![LetterState::Unknown; alphabet.size()];
+    for guess in past {
+        for (&letter, correctness) in guess.word.iter().zip(guess.mask) {
+            let Some(index) = alphabet.index(letter) else {
+                continue;
+            };
+
+            let state = match correctness {
+                Correctness::Correct => LetterState::Placed,
+                Correctness::Misplaced => LetterState::Present,
+                Correctness::Wrong => LetterState::Absent,
+            };
+            let known = &mut states[index];
+            *known = (*known).max(state);
+        }
+    }
+
+    states
+}
+
+/// How many of `words` have each letter from 'a' to 'z' in each position, which is how common a
+/// letter is in a spot, without looking at the masks a guess could produce. Bytes that aren't
+/// lowercase ASCII letters aren't counted.
+///
+/// This takes anything that iterates over words, like a `Dictionary` or the keys of a
+/// `DictionaryWithCounts`.
+pub fn positional_frequencies<'a, 'w: 'a, const N: usize>(
+    words: impl IntoIterator<Item = &'a &'w Word<N>>,
+) -> [[u32; 26]; N] {
+    positional_frequencies_in(words, &AsciiLowercase).map(|frequencies| {
+        frequencies
+            .try_into()
+            .expect("There is a count for every letter of the alphabet")
+    })
+}
+
+/// Like `positional_frequencies`, but for the letters of `alphabet`, by their index. Bytes that
+/// aren't in the alphabet aren't counted.
+pub fn positional_frequencies_in<'a, 'w: 'a, const N: usize>(
+    words: impl IntoIterator<Item = &'a &'w Word<N>>,
+    alphabet: &impl Alphabet,
+) -> [Vec<u32>; N] {
+    let mut frequencies = std::array::from_fn(|_| vec![0; alphabet.size()]);
+    for word in words {
+        for (position, &letter) in word.iter().enumerate() {
+            if let Some(index) = alphabet.index(letter) {
+                frequencies[position][index] += 1;
+            }
+        }
+    }
+
+    frequencies
+}
+
+/// The formats `Wordle::evaluate_to_writer` can write its rows in.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum OutputFormat {
+    /// A header line, then one comma-separated line per game, with the guesses separated by spaces.
+    /// Fields with a comma, a quote or a line break in them are quoted.
+    Csv,
+    /// A single JSON array with one object per game.
+    Json,
+}
+
+/// How many games took how many guesses, which is how solvers are usually compared.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct ScoreDistribution {
+    /// `counts[i]` is the number of games won in exactly `i` guesses, so `counts[0]` is always 0.
+    pub counts: [usize; TRIES_BEFORE_LOSS + 1],
+    /// The number of games that were not won.
+    pub losses: usize,
+}
+
+impl ScoreDistribution {
+    /// Adds the outcome of one game, as returned by `Wordle::play`.
+    pub fn record(&mut self, outcome: Option<usize>) {
+        match outcome {
+            Some(guesses) if guesses < self.counts.len() => self.counts[guesses] += 1,
+            _ => self.losses += 1,
+        }
+    }
+
+    /// The number of games played, won or lost.
+    pub fn games(&self) -> usize {
+        self.counts.iter().sum::<usize>() + self.losses
+    }
+
+    /// The average number of guesses over the games that were won, or `None` if none were.
+    pub fn mean(&self) -> Option<f64> {
+        let wins = self.counts.iter().sum::<usize>();
+        let guesses = self
+            .counts
+            .iter()
+            .enumerate()
+            .map(|(guesses, count)| guesses * count)
+            .sum::<usize>();
+
+        (wins > 0).then(|| guesses as f64 / wins as f64)
+    }
+
+    /// The fraction of all games that were won in at most `cutoff` guesses, or `None` if no games
+    /// were played. The real game has a cutoff of 6.
+    pub fn win_rate(&self, cutoff: usize) -> Option<f64> {
+        let wins = self.counts.iter().take(cutoff + 1).sum::<usize>();
+        let games = self.games();
+
+        (games > 0).then(|| wins as f64 / games as f64)
+    }
+}
+
+impl Default for ScoreDistribution {
+    fn default() -> Self {
+        Self {
+            counts: [0; TRIES_BEFORE_LOSS + 1],
+            losses: 0,
+        }
+    }
+}
+
+impl FromIterator<Option<usize>> for ScoreDistribution {
+    fn from_iter<I: IntoIterator<Item = Option<usize>>>(outcomes: I) -> Self {
+        let mut distribution = Self::default();
+        for outcome in outcomes {
+            distribution.record(outcome);
+        }
+        distribution
+    }
+}
+
+/// The widest bar of the histogram printed by `ScoreDistribution`'s `Display`.
+const HISTOGRAM_WIDTH: usize = 50;
+
+/// Prints one bar per number of guesses, up to the largest number any game took, followed by the
+/// losses and a summary line.
+impl fmt::Display for ScoreDistribution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let most = self
+            .counts
+            .iter()
+            .max()
+            .copied()
+            .unwrap_or(0)
+            .max(self.losses);
+        let bar = |count: usize| "#".repeat((count * HISTOGRAM_WIDTH).div_ceil(most.max(1)));
+        let longest = self
+            .counts
+            .iter()
+            .rposition(|&count| count > 0)
+            .unwrap_or(0);
+
+        for (guesses, &count) in self.counts.iter().enumerate().take(longest + 1).skip(1) {
+            writeln!(f, "{:>4} | {} {}", guesses, bar(count), count)?;
+        }
+        writeln!(f, "lost | {} {}", bar(self.losses), self.losses)?;
+
+        match self.mean() {
+            Some(mean) => write!(f, "mean {:.4}, ", mean)?,
+            None => write!(f, "no wins, ")?,
+        }
+        match self.win_rate(6) {
+            Some(win_rate) => write!(f, "{:.2}% won within 6", 100.0 * win_rate),
+            None => write!(f, "no games"),
+        }
+    }
+}
+
+/// The mean and variance of a stream of scores, kept up to date one score at a time with
+/// Welford's algorithm, so that long runs can report them as they go without keeping every score.
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct RunningStats {
+    count: usize,
+    mean: f64,
+    /// The sum of the squared differences of every score from the mean.
+    squared_differences: f64,
+}
+
+impl RunningStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a score, e.g. the number of guesses a game that was won took.
+    pub fn push(&mut self, score: usize) {
+        let score = score as f64;
+        self.count += 1;
+
+        let difference = score - self.mean;
+        self.mean += difference / self.count as f64;
+        // Taking one difference from the old mean and one from the new one is what keeps this
+        // from losing precision the way summing up squares does.
+        self.squared_differences += difference * (score - self.mean);
+    }
+
+    /// The number of scores pushed so far.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// The average of the scores, or `None` if there are none.
+    pub fn mean(&self) -> Option<f64> {
+        (self.count > 0).then_some(self.mean)
+    }
+
+    /// The population variance of the scores, or `None` if there are none.
+    pub fn variance(&self) -> Option<f64> {
+        (self.count > 0).then(|| self.squared_differences / self.count as f64)
+    }
+
+    /// The population standard deviation of the scores, or `None` if there are none.
+    pub fn stddev(&self) -> Option<f64> {
+        self.variance().map(f64::sqrt)
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum GameMode {
+    /// Any word in the dictionary can be guessed at any time.
+    Normal,
+    /// Every guess has to use what the masks of the previous guesses revealed, as strictly as the
+    /// `Strictness` says.
+    Hard(Strictness),
+}
+
+/// How closely `GameMode::Hard` holds every guess to the masks before it.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Strictness {
+    /// Greens have to stay in their spot, and yellow letters have to be used again, with at least
+    /// as many copies as were green or yellow. Gray letters can be used again, and yellow ones can
+    /// go back where they were. This is the rule of the hard mode of Wordle itself.
+    Loose,
+    /// Every guess has to still be a possible answer, so every count a mask gives has to hold,
+    /// including the ones that gray copies give, and no letter can go where it was yellow or gray.
+    Strict,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub enum PlayError<const N: usize = WORD_SIZE> {
+    /// The guesser guessed a word that is not in the dictionary on the given attempt.
+    IllegalGuess { attempt: usize, word: Word<N> },
+    /// The guesser guessed a word that contradicts a previous mask while playing in hard mode.
+    HardModeViolation { attempt: usize, word: Word<N> },
+    /// The answer is not in the dictionary, so the game could never be won, or not one of the
+    /// possible answers, so the guessers would rule it out.
+    UnknownAnswer { word: Word<N> },
+    /// No possible answer fits the masks of the guesses before the given attempt, which leaves
+    /// guessers that guess among the possible answers with nothing to guess.
+    NoPossibleAnswers { attempt: usize },
+}
+
+/// Shows words as the text they spell instead of as their bytes, like `WordDisplay`.
+impl<const N: usize> fmt::Debug for PlayError<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::IllegalGuess { attempt, word } => f
+                .debug_struct("IllegalGuess")
+                .field("attempt", attempt)
+                .field("word", &WordDisplay(word))
+                .finish(),
+            Self::HardModeViolation { attempt, word } => f
+                .debug_struct("HardModeViolation")
+                .field("attempt", attempt)
+                .field("word", &WordDisplay(word))
+                .finish(),
+            Self::UnknownAnswer { word } => f
+                .debug_struct("UnknownAnswer")
+                .field("word", &WordDisplay(word))
+                .finish(),
+            Self::NoPossibleAnswers { attempt } => f
+                .debug_struct("NoPossibleAnswers")
+                .field("attempt", attempt)
+                .finish(),
+        }
+    }
+}
+
+impl<const N: usize> fmt::Display for PlayError<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::IllegalGuess { attempt, word } => write!(
+                f,
+                "guess {} was {:?}, which is not in the dictionary",
+                attempt,
+                WordDisplay(word)
+            ),
+            Self::HardModeViolation { attempt, word } => write!(
+                f,
+                "guess {} was {:?}, which contradicts an earlier mask in hard mode",
+                attempt,
+                WordDisplay(word)
+            ),
+            Self::UnknownAnswer { word } => write!(
+                f,
+                "the answer {:?} is not in the dictionary or not a possible answer",
+                WordDisplay(word)
+            ),
+            Self::NoPossibleAnswers { attempt } => write!(
+                f,
+                "no possible answer is left to make guess {} from",
+                attempt
+            ),
+        }
+    }
+}
+
+impl<const N: usize> std::error::Error for PlayError<N> {}
+
+/// Every way the crate can fail, for applications that would rather handle one error type than
+/// each of the ones the crate returns. All of them convert into this one, so `?` works on any of
+/// them in a function returning a `RogetError`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum RogetError<const N: usize = WORD_SIZE> {
+    /// A game couldn't be played by its rules, like when a guess or the answer is not in the
+    /// dictionary.
+    IllegalGuess(PlayError<N>),
+    /// There are no words to play with.
+    EmptyDictionary,
+    /// A word couldn't be read.
+    ParseWord(WordError),
+    /// A word list has a word without a count, or with a count that isn't a number, on the given
+    /// line.
+    ParseCount { line: usize },
+    /// The word lists don't fit together, like when a word appears twice or an answer has no
+    /// count, see `WordleBuildError`.
+    WordList(WordleBuildError),
+    /// A mask couldn't be read.
+    ParseMask(ParseMaskError),
+    /// No possible answer fits the masks of the guesses before the given attempt, so the masks
+    /// contradict each other or the word lists.
+    MaskInconsistent { attempt: usize },
+}
+
+impl<const N: usize> fmt::Display for RogetError<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::IllegalGuess(error) => write!(f, "{}", error),
+            Self::EmptyDictionary => write!(f, "{}", WordleError::EmptyDictionary),
+            Self::ParseWord(error) => write!(f, "{}", error),
+            Self::ParseCount { line } => {
+                write!(
+                    f,
+                    "line {}: expected a word and a count that is a number",
+                    line
+                )
+            }
+            Self::WordList(error) => write!(f, "{}", error),
+            Self::ParseMask(error) => write!(f, "{}", error),
+            Self::MaskInconsistent { attempt } => write!(
+                f,
+                "{}",
+                PlayError::<N>::NoPossibleAnswers { attempt: *attempt }
+            ),
+        }
+    }
+}
+
+impl<const N: usize> std::error::Error for RogetError<N> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::IllegalGuess(error) => Some(error),
+            Self::ParseWord(error) => Some(error),
+            Self::WordList(error) => Some(error),
+            Self::ParseMask(error) => Some(error),
+            Self::EmptyDictionary | Self::ParseCount { .. } | Self::MaskInconsistent { .. } => None,
+        }
+    }
+}
+
+impl<const N: usize> From<PlayError<N>> for RogetError<N> {
+    fn from(error: PlayError<N>) -> Self {
+        match error {
+            PlayError::NoPossibleAnswers { attempt } => Self::MaskInconsistent { attempt },
+            error => Self::IllegalGuess(error),
+        }
+    }
+}
+
+impl<const N: usize> From<WordleError> for RogetError<N> {
+    fn from(error: WordleError) -> Self {
+        match error {
+            WordleError::EmptyDictionary => Self::EmptyDictionary,
+        }
+    }
+}
+
+impl<const N: usize> From<WordError> for RogetError<N> {
+    fn from(error: WordError) -> Self {
+        Self::ParseWord(error)
+    }
+}
+
+impl<const N: usize> From<LoadWarning> for RogetError<N> {
+    fn from(warning: LoadWarning) -> Self {
+        match warning {
+            LoadWarning::InvalidWord { error, .. } => Self::ParseWord(error),
+            LoadWarning::MissingCount { line } | LoadWarning::InvalidCount { line } => {
+                Self::ParseCount { line }
+            }
+            LoadWarning::Duplicate { .. } => Self::WordList(WordleBuildError::InvalidLine(warning)),
+        }
+    }
+}
+
+impl<const N: usize> From<WordleBuildError> for RogetError<N> {
+    fn from(error: WordleBuildError) -> Self {
+        match error {
+            WordleBuildError::InvalidLine(warning) => Self::from(warning),
+            error => Self::WordList(error),
+        }
+    }
+}
+
+impl<const N: usize> From<ParseMaskError> for RogetError<N> {
+    fn from(error: ParseMaskError) -> Self {
+        Self::ParseMask(error)
+    }
+}
+
+impl<const N: usize> From<TranscriptError> for RogetError<N> {
+    fn from(error: TranscriptError) -> Self {
+        match error {
+            TranscriptError::Word { error, .. } => Self::ParseWord(error),
+            TranscriptError::Mask { error, .. } => Self::ParseMask(error),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum Correctness {
+    /// Green
+    Correct,
+    /// Yellow
+    Misplaced,
+    /// Gray
+    Wrong,
+}
+
+impl Correctness {
+    pub const fn check<const N: usize>(answer: &Word<N>, guessed_word: &Word<N>) -> [Self; N] {
+        let mut rv = [Self::Wrong; N];
+        Self::check_into(answer, guessed_word, &mut rv);
+
+        rv
+    }
+
+    /// Like `check`, but writes the mask into `out` so that tight loops can reuse one array. This
+    /// is a `const fn`, which is why it sticks to `while` loops and `matches!`.
+    pub const fn check_into<const N: usize>(
+        answer: &Word<N>,
+        guessed_word: &Word<N>,
+        out: &mut [Self; N],
+    ) {
+        let mut used = [false; N];
+        let mut i = 0;
+        while i < N {
+            if answer[i] == guessed_word[i] {
+                out[i] = Self::Correct;
+                used[i] = true;
+            } else {
+                out[i] = Self::Wrong;
+            }
+            i += 1;
+        }
+
+        // Check can't just check for misplaced using: `answer.contains(&guessed_word[i])`
+        // because it takes care of counts when deciding whether it is misplaced or wrong.
+        let mut i = 0;
+        while i < N {
+            let mut j = 0;
+            while j < N {
+                if !matches!(out[i], Self::Correct) && !used[j] && answer[j] == guessed_word[i] {
+                    out[i] = Self::Misplaced;
+                    used[j] = true;
+                    break;
+                }
+                j += 1;
+            }
+            i += 1;
+        }
+    }
+
+    /// Like `check`, but accumulates the mask straight into a `PackedCorrectness` so that no
+    /// intermediate array needs to be materialized.
+    pub fn check_packed(answer: &Word, guessed_word: &Word) -> PackedCorrectness {
+        let greens = green_bits(answer, guessed_word);
+        let mut used = [false; WORD_SIZE];
+        for (i, used) in used.iter_mut().enumerate() {
+            *used = greens & (1 << i) != 0;
+        }
+
+        let mut packed = 0;
+        for (i, &letter) in guessed_word.iter().enumerate() {
+            let correctness = if greens & (1 << i) != 0 {
+                Self::Correct
+            } else if let Some(j) = (0..WORD_SIZE).find(|&j| !used[j] && answer[j] == letter) {
+                used[j] = true;
+                Self::Misplaced
+            } else {
+                Self::Wrong
+            };
+
+            packed = packed * 3 + correctness as u8;
+        }
+
+        PackedCorrectness(packed)
+    }
+
+    /// Returns whether some answer could have produced `mask` when guessing `guess`. Masks coming
+    /// from `check` always are, but hand-typed ones (e.g. from `from_emoji`) might not be, and
+    /// should be rejected before being fed to a guesser.
+    pub fn is_consistent(guess: &Word, mask: &[Self; WORD_SIZE]) -> bool {
+        // `check` hands yellows out to the leftmost non-green copies of a letter first, so a gray
+        // copy of a letter before a yellow copy of that same letter can never happen.
+        for i in 0..WORD_SIZE {
+            if mask[i] == Self::Wrong
+                && (i + 1..WORD_SIZE).any(|j| guess[j] == guess[i] && mask[j] == Self::Misplaced)
+            {
+                return false;
+            }
+        }
+
+        // Every yellow needs its letter to sit in a spot of the answer that is not green and where
+        // the guess has a different letter. We look for such spots for all yellows at once by
+        // matching yellows to spots.
+        let mut spot_owners = [None; WORD_SIZE];
+        for yellow in (0..WORD_SIZE).filter(|&i| mask[i] == Self::Misplaced) {
+            let mut visited = [false; WORD_SIZE];
+            if !Self::assign_spot(guess, mask, yellow, &mut visited, &mut spot_owners) {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// Finds a spot for `yellow`, possibly by moving yellows that already own a spot elsewhere.
+    fn assign_spot(
+        guess: &Word,
+        mask: &[Self; WORD_SIZE],
+        yellow: usize,
+        visited: &mut [bool; WORD_SIZE],
+        spot_owners: &mut [Option<usize>; WORD_SIZE],
+    ) -> bool {
+        for spot in 0..WORD_SIZE {
+            if visited[spot] || mask[spot] == Self::Correct || guess[spot] == guess[yellow] {
+                continue;
+            }
+
+            visited[spot] = true;
+            if spot_owners[spot]
+                .is_none_or(|owner| Self::assign_spot(guess, mask, owner, visited, spot_owners))
+            {
+                spot_owners[spot] = Some(yellow);
+                return true;
+            }
+        }
+
+        false
+    }
+
+    /// Parses a row of a Wordle share string, mapping 🟩 to Correct, 🟨 to Misplaced, and either
+    /// ⬛ (dark mode) or ⬜ (light mode) to Wrong. Emoji variation selectors are ignored. Rows from
+    /// the high contrast theme, with 🟧 for Correct and 🟦 for Misplaced, parse too.
+    pub fn from_emoji(s: &str) -> Result<[Self; WORD_SIZE], ParseMaskError> {
+        Self::from_symbols(s, |symbol| match symbol {
+            '🟩' | '🟧' => Ok(Self::Correct),
+            '🟨' | '🟦' => Ok(Self::Misplaced),
+            '⬛' | '⬜' => Ok(Self::Wrong),
+            _ => Err(ParseMaskError::UnknownSymbol(symbol)),
+        })
+    }
+
+    /// Parses a mask written with one letter per position, as accepted by `from_letter`, such as
+    /// "WMCWW" or "bygbb".
+    pub fn from_letters(s: &str) -> Result<[Self; WORD_SIZE], ParseMaskError> {
+        Self::from_symbols(s, Self::from_letter)
+    }
+
+    /// Parses a mask written either in emoji or in letters, whichever `s` looks like.
+    pub fn parse_mask(s: &str) -> Result<[Self; WORD_SIZE], ParseMaskError> {
+        if s.is_ascii() {
+            Self::from_letters(s)
+        } else {
+            Self::from_emoji(s)
+        }
+    }
+
+    fn from_symbols(
+        s: &str,
+        parse: impl Fn(char) -> Result<Self, ParseMaskError>,
+    ) -> Result<[Self; WORD_SIZE], ParseMaskError> {
+        let mut rv = [Self::Wrong; WORD_SIZE];
+        let mut len = 0;
+        for symbol in s.trim().chars().filter(|&symbol| symbol != '\u{FE0F}') {
+            let correctness = parse(symbol)?;
+
+            if len < WORD_SIZE {
+                rv[len] = correctness;
+            }
+            len += 1;
+        }
+
+        if len != WORD_SIZE {
+            return Err(ParseMaskError::WrongLength(len));
+        }
+
+        Ok(rv)
+    }
+
+    /// Renders a mask the way Wordle's clipboard export does. Wrong renders as ⬛ in dark mode and
+    /// as ⬜ otherwise.
+    pub fn to_emoji<const N: usize>(mask: &[Self; N], dark_mode: bool) -> String {
+        Self::render(mask, RenderStyle::Emoji, dark_mode)
+    }
+
+    /// Renders a mask in `style`. The emoji styles draw Wrong as ⬛ in dark mode and as ⬜
+    /// otherwise, while `RenderStyle::Ascii` writes the letters of `as_letter` either way.
+    pub fn render<const N: usize>(mask: &[Self; N], style: RenderStyle, dark_mode: bool) -> String {
+        mask.iter()
+            .map(|&correctness| match (correctness, style) {
+                (_, RenderStyle::Ascii) => correctness.as_letter(),
+                (Self::Correct, RenderStyle::ColorblindEmoji) => '🟧',
+                (Self::Misplaced, RenderStyle::ColorblindEmoji) => '🟦',
+                (Self::Correct, _) => '🟩',
+                (Self::Misplaced, _) => '🟨',
+                (Self::Wrong, _) if dark_mode => '⬛',
+                (Self::Wrong, _) => '⬜',
+            })
+            .collect()
+    }
+
+    /// The letter a mask position is written as in text: `C`orrect, `M`isplaced, or `W`rong.
+    pub fn as_letter(self) -> char {
+        match self {
+            Self::Correct => 'C',
+            Self::Misplaced => 'M',
+            Self::Wrong => 'W',
+        }
+    }
+
+    /// The inverse of `as_letter`, which also accepts lowercase letters, and the colors `G`reen,
+    /// `Y`ellow and `B`lack that people tend to type instead.
+    pub fn from_letter(letter: char) -> Result<Self, ParseMaskError> {
+        match letter.to_ascii_uppercase() {
+            'C' | 'G' => Ok(Self::Correct),
+            'M' | 'Y' => Ok(Self::Misplaced),
+            'W' | 'B' => Ok(Self::Wrong),
+            _ => Err(ParseMaskError::UnknownSymbol(letter)),
+        }
+    }
+
+    /// The index of `mask` among `all_masks`, from 0 for all green to `PackedCorrectness::COUNT - 1`
+    /// for all gray, for looking masks up in arrays instead of maps. This is the index of the
+    /// packed mask.
+    pub fn to_index(mask: &[Self; WORD_SIZE]) -> usize {
+        PackedCorrectness::from(*mask).as_index()
+    }
+
+    /// The inverse of `to_index`.
+    ///
+    /// Panics if `index` is not below `PackedCorrectness::COUNT`.
+    pub fn from_index(index: usize) -> [Self; WORD_SIZE] {
+        u8::try_from(index)
+            .ok()
+            .and_then(|index| PackedCorrectness::try_from(index).ok())
+            .unwrap_or_else(|| panic!("There is no mask with index {}", index))
+            .unpack()
+    }
+
+    /// Every mask there is, from all green to all gray, by counting through every packed mask in
+    /// base 3.
+    pub fn all_masks() -> impl Iterator<Item = [Self; WORD_SIZE]> {
+        (0..PackedCorrectness::COUNT).map(|packed| PackedCorrectness(packed as u8).unpack())
+    }
+
+    fn from_trit(trit: u8) -> Self {
+        match trit {
+            0 => Self::Correct,
+            1 => Self::Misplaced,
+            _ => Self::Wrong,
+        }
+    }
+}
+
+/// Returns a bitmask with bit `i` set whenever `answer[i] == guessed_word[i]`, which is the green
+/// pass of `Correctness::check_packed`. With the `simd` feature on an SSE2 target, this compares
+/// all positions with a single vector instruction.
+#[inline]
+fn green_bits(answer: &Word, guessed_word: &Word) -> u32 {
+    #[cfg(all(feature = "simd", target_feature = "sse2"))]
+    return green_bits_sse2(answer, guessed_word);
+
+    #[cfg(not(all(feature = "simd", target_feature = "sse2")))]
+    return green_bits_scalar(answer, guessed_word);
+}
+
+#[cfg(any(test, not(all(feature = "simd", target_feature = "sse2"))))]
+fn green_bits_scalar(answer: &Word, guessed_word: &Word) -> u32 {
+    let mut bits = 0;
+    for i in 0..WORD_SIZE {
+        if answer[i] == guessed_word[i] {
+            bits |= 1 << i;
+        }
+    }
+
+    bits
+}
+
+#[cfg(all(feature = "simd", target_feature = "sse2"))]
+fn green_bits_sse2(answer: &Word, guessed_word: &Word) -> u32 {
+    #[cfg(target_arch = "x86")]
+    use std::arch::x86::{__m128i, _mm_cmpeq_epi8, _mm_loadu_si128, _mm_movemask_epi8};
+    #[cfg(target_arch = "x86_64")]
+    use std::arch::x86_64::{__m128i, _mm_cmpeq_epi8, _mm_loadu_si128, _mm_movemask_epi8};
+
+    // Words are shorter than a vector, so we pad both into 16-byte buffers. The padding compares
+    // equal, which is why we mask the result down to `WORD_SIZE` bits.
+    let mut answer_lanes = [0u8; 16];
+    let mut guess_lanes = [0u8; 16];
+    answer_lanes[..WORD_SIZE].copy_from_slice(answer);
+    guess_lanes[..WORD_SIZE].copy_from_slice(guessed_word);
+
+    // SAFETY: SSE2 is enabled at compile time per the cfg above, and the loads are unaligned
+    // loads of two 16-byte buffers we own.
+    let bits = unsafe {
+        _mm_movemask_epi8(_mm_cmpeq_epi8(
+            _mm_loadu_si128(answer_lanes.as_ptr() as *const __m128i),
+            _mm_loadu_si128(guess_lanes.as_ptr() as *const __m128i),
+        ))
+    };
+
+    bits as u32 & ((1 << WORD_SIZE) - 1)
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ParseMaskError {
+    /// The mask did not have exactly `WORD_SIZE` symbols; holds the number it had.
+    WrongLength(usize),
+    /// The mask contained a symbol that does not represent any `Correctness`.
+    UnknownSymbol(char),
+}
+
+impl fmt::Display for ParseMaskError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::WrongLength(len) => write!(
+                f,
+                "expected a mask of {} symbols but found {}",
+                WORD_SIZE, len
+            ),
+            Self::UnknownSymbol(symbol) => write!(f, "unknown mask symbol {:?}", symbol),
+        }
+    }
+}
+
+impl std::error::Error for ParseMaskError {}
+
+/// A mask packed into a single byte by treating each position's `Correctness` as a base-3 digit,
+/// with the first position being the most significant. Packed masks are cheap to hash and can
+/// be used to index into an array of length `PackedCorrectness::COUNT`.
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct PackedCorrectness(u8);
+
+impl PackedCorrectness {
+    /// The number of distinct masks, which is `3^WORD_SIZE`.
+    pub const COUNT: usize = 3usize.pow(WORD_SIZE as u32);
+
+    pub fn as_u8(self) -> u8 {
+        self.0
+    }
+
+    pub fn as_index(self) -> usize {
+        self.0 as usize
+    }
+
+    pub fn unpack(self) -> [Correctness; WORD_SIZE] {
+        let mut rv = [Correctness::Wrong; WORD_SIZE];
+        let mut remainder = self.0;
+        for i in (0..WORD_SIZE).rev() {
+            rv[i] = Correctness::from_trit(remainder % 3);
+            remainder /= 3;
+        }
+
+        rv
+    }
+}
+
+impl From<[Correctness; WORD_SIZE]> for PackedCorrectness {
+    fn from(mask: [Correctness; WORD_SIZE]) -> Self {
+        Self(
+            mask.iter()
+                .fold(0, |packed, &correctness| packed * 3 + correctness as u8),
+        )
+    }
+}
+
+impl From<PackedCorrectness> for [Correctness; WORD_SIZE] {
+    fn from(packed: PackedCorrectness) -> Self {
+        packed.unpack()
+    }
+}
+
+impl TryFrom<u8> for PackedCorrectness {
+    type Error = u8;
+
+    /// Fails, handing back the byte, if it is not below `PackedCorrectness::COUNT`.
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        if (value as usize) < Self::COUNT {
+            Ok(Self(value))
+        } else {
+            Err(value)
+        }
+    }
+}
+
+impl fmt::Display for PackedCorrectness {
+    /// Displays the mask as one letter per position: `C`orrect, `M`isplaced, or `W`rong.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for correctness in self.unpack() {
+            write!(f, "{}", correctness.as_letter())?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A dense table of precomputed masks for every (answer, guess) pair of a word list, so that
+/// guessers can look masks up instead of calling `Correctness::check` in their hot loops.
+///
+/// The table stores one byte per pair, so caching the full 12947-word dictionary against itself
+/// costs roughly 168MB. Use `CorrectnessCache::builder` to only cache a subset of the words as
+/// answers (e.g. the 2309 possible answers, for roughly 30MB) if that is too much.
+pub struct CorrectnessCache<'w> {
+    answer_indices: HashMap<&'w Word, usize>,
+    guess_indices: HashMap<&'w Word, usize>,
+    masks: Vec<PackedCorrectness>,
+}
+
+impl<'w> CorrectnessCache<'w> {
+    /// Caches every word in `words` against every other word in `words`.
+    pub fn new(words: &[&'w Word]) -> Self {
+        Self::builder(words).build()
+    }
+
+    pub fn builder(guesses: &[&'w Word]) -> CorrectnessCacheBuilder<'w> {
+        CorrectnessCacheBuilder {
+            guesses: guesses.to_vec(),
+            answers: None,
+        }
+    }
+
+    pub fn answer_index(&self, answer: &Word) -> Option<usize> {
+        self.answer_indices.get(answer).copied()
+    }
+
+    pub fn guess_index(&self, guess: &Word) -> Option<usize> {
+        self.guess_indices.get(guess).copied()
+    }
+
+    /// Returns the mask that guessing the word at `guess_idx` yields when the answer is the word at
+    /// `answer_idx`.
+    pub fn get(&self, answer_idx: usize, guess_idx: usize) -> PackedCorrectness {
+        self.masks[answer_idx * self.guess_indices.len() + guess_idx]
+    }
+
+    /// Looks a mask up by its words, returning `None` if either word is not cached.
+    pub fn get_by_word(&self, answer: &Word, guess: &Word) -> Option<PackedCorrectness> {
+        Some(self.get(self.answer_index(answer)?, self.guess_index(guess)?))
+    }
+
+    /// The number of masks stored, which is also the number of bytes the table takes up.
+    pub fn len(&self) -> usize {
+        self.masks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.masks.is_empty()
+    }
+}
+
+pub struct CorrectnessCacheBuilder<'w> {
+    guesses: Vec<&'w Word>,
+    answers: Option<Vec<&'w Word>>,
+}
+
+impl<'w> CorrectnessCacheBuilder<'w> {
+    /// Only caches masks for these answers instead of for every guess.
+    pub fn answers(mut self, answers: &[&'w Word]) -> Self {
+        self.answers = Some(answers.to_vec());
+        self
+    }
+
+    pub fn build(self) -> CorrectnessCache<'w> {
+        let answers = self.answers.unwrap_or_else(|| self.guesses.clone());
+
+        let mut masks = Vec::with_capacity(answers.len() * self.guesses.len());
+        for answer in &answers {
+            for guess in &self.guesses {
+                masks.push(Correctness::check_packed(answer, guess));
+            }
+        }
+
+        CorrectnessCache {
+            answer_indices: answers.iter().enumerate().map(|(i, &w)| (w, i)).collect(),
+            guess_indices: self
+                .guesses
+                .iter()
+                .enumerate()
+                .map(|(i, &w)| (w, i))
+                .collect(),
+            masks,
+        }
+    }
+}
+
+/// A guessed word and the mask it got. Guesses compare and hash by their word and mask, so a
+/// slice of them, the history of a game, can key a cache of game states.
+#[derive(Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Guess<'w, const N: usize = WORD_SIZE> {
+    pub word: &'w Word<N>,
+    pub mask: [Correctness; N],
+}
+
+impl<'w, const N: usize> Guess<'w, N> {
+    /// Guesses `word` against `answer`, which computes the mask, so the two can't be mixed up.
+    pub fn new(answer: &'w Word<N>, word: &'w Word<N>) -> Self {
+        Self {
+            word,
+            mask: Correctness::check(answer, word),
+        }
+    }
+
+    /// A guess with a mask that's already known, e.g. when replaying a game that was played
+    /// elsewhere.
+    pub fn from_mask(word: &'w Word<N>, mask: [Correctness; N]) -> Self {
+        Self { word, mask }
+    }
+
+    /// Returns whether `candidate` could still be the answer given this guess's mask, i.e. whether
+    /// guessing this word against `candidate` would produce the same mask.
+    pub fn allows(&self, candidate: &Word<N>) -> bool {
+        self.allows_with(candidate, Strictness::Strict)
+    }
+
+    /// Like `allows`, at the given `strictness`, for telling whether hard mode lets `candidate`
+    /// be guessed after this guess. `Strictness::Strict` is `allows`, while `Strictness::Loose`
+    /// only asks for the greens and the letters that were revealed, see
+    /// `Constraints::matches_with`.
+    pub fn allows_with(&self, candidate: &Word<N>, strictness: Strictness) -> bool {
+        Constraints::from_guesses(std::slice::from_ref(self)).matches_with(candidate, strictness)
+    }
+
+    /// Returns the words of `dictionary` that could still be the answer given every guess in
+    /// `past`, in the order `dictionary` yields them.
+    pub fn filter_candidates<'a, 'd: 'a>(
+        past: &[Guess<'_, N>],
+        dictionary: impl IntoIterator<Item = &'a &'d Word<N>>,
+    ) -> Vec<&'d Word<N>> {
+        let constraints = Constraints::from_guesses(past);
+
+        dictionary
+            .into_iter()
+            .copied()
+            .filter(|candidate| constraints.matches(candidate))
+            .collect()
+    }
+}
+
+impl<const N: usize> fmt::Debug for Guess<'_, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Guess")
+            .field("word", &WordDisplay(self.word))
+            .field("mask", &self.mask)
+            .finish()
+    }
+}
+
+impl<const N: usize> fmt::Display for Guess<'_, N> {
+    /// Displays the word followed by its mask in letters, e.g. "crane WMCWW".
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ", WordDisplay(self.word))?;
+        for correctness in self.mask {
+            write!(f, "{}", correctness.as_letter())?;
+        }
+
+        Ok(())
+    }
+}
+
+pub trait Guesser<'w, const N: usize = WORD_SIZE> {
+    /// The word to guess after `past_guesses`, oldest first.
+    ///
+    /// No word a guesser knows of may fit the past guesses, if the answer isn't one of the words
+    /// it was told could be, or the masks contradict each other. The guessers of this crate then
+    /// guess the first word, alphabetically, of the words they guess from, instead of panicking.
+    fn guess(&mut self, past_guesses: &[Guess<'w, N>]) -> &'w Word<N>;
+
+    /// Like `guess`, but also told how far into the game it is, for guessers that play
+    /// differently depending on how constrained the game is. By default the context is ignored.
+    ///
+    /// `Wordle::play` and every other way of playing a game call this rather than `guess`.
+    fn guess_with_context(
+        &mut self,
+        past_guesses: &[Guess<'w, N>],
+        _context: &GameContext,
+    ) -> &'w Word<N> {
+        self.guess(past_guesses)
+    }
+
+    /// How many bits of information the guesser expected its last guess to give, for guessers
+    /// that pick their guesses by it. `Wordle::play_recorded` keeps this for every guess, see
+    /// `GameRecord::information`. By default the guesser doesn't say.
+    fn last_expected_information(&self) -> Option<f64> {
+        None
+    }
+
+    /// What to call this guesser in the rows `Wordle::evaluate_to_writer` writes. By default,
+    /// this is the name of its type, which the guessers of this crate replace with a shorter one.
+    fn name(&self) -> &'static str {
+        std::any::type_name::<Self>()
+    }
+
+    /// Gets ready for a new game, so that one guesser can play one game after the other instead
+    /// of being built anew for every game. By default this does nothing, which is only right for
+    /// guessers that go by nothing but the past guesses they're given, like `Clairvoyant`. Any
+    /// guesser that keeps something from one guess to the next, like the words that could still
+    /// be the answer, has to put it back the way it was before the first guess.
+    fn reset(&mut self) {}
+}
+
+/// A guesser for `MultiWordle`, which makes one guess for every board at once.
+pub trait MultiGuesser<'w, const N: usize = WORD_SIZE> {
+    /// Takes the past guesses of every board, in the order of the answers. A board is solved once
+    /// its last guess came back all green, and gets no more guesses after that.
+    fn guess(&mut self, boards: &[Vec<Guess<'w, N>>]) -> &'w Word<N>;
+}
+
+/// What a game tells a guesser on top of the past guesses, see `Guesser::guess_with_context`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct GameContext {
+    /// The 1-based number of the guess about to be made.
+    pub attempt: usize,
+    /// How many of the game's possible answers fit every past guess, if the caller knows. Games
+    /// played through `Wordle` always know, and narrow their possible answers down after every
+    /// guess to keep count.
+    pub remaining_hint: Option<usize>,
+}
+
+/// We want to allow functions to be guessers, which just calls `self` on `past_guesses`.
+impl<'w, const N: usize> Guesser<'w, N> for fn(past_guesses: &[Guess<'_, N>]) -> &'w Word<N> {
+    fn guess(&mut self, past_guesses: &[Guess<'w, N>]) -> &'w Word<N> {
+        (*self)(past_guesses)
+    }
+
+    fn name(&self) -> &'static str {
+        "function"
+    }
+}
+
+/// Lets a guesser be lent to a game and used again after it, see `Guesser::reset`.
+impl<'w, G: Guesser<'w, N> + ?Sized, const N: usize> Guesser<'w, N> for &mut G {
+    fn guess(&mut self, past_guesses: &[Guess<'w, N>]) -> &'w Word<N> {
+        (**self).guess(past_guesses)
+    }
+
+    fn guess_with_context(
+        &mut self,
+        past_guesses: &[Guess<'w, N>],
+        context: &GameContext,
+    ) -> &'w Word<N> {
+        (**self).guess_with_context(past_guesses, context)
+    }
+
+    fn last_expected_information(&self) -> Option<f64> {
+        (**self).last_expected_information()
+    }
+
+    fn name(&self) -> &'static str {
+        (**self).name()
+    }
+
+    fn reset(&mut self) {
+        (**self).reset()
+    }
+}
+
+/// Guesses the words of a script in order, whatever the masks say, for tests that need a game to
+/// go a certain way, including tests of solvers outside this crate. It goes by how many guesses
+/// were made, so the same guesser can play any number of games.
+///
+/// Panics if asked for more guesses than the script has.
+#[derive(Debug, Clone)]
+pub struct ScriptedGuesser<'w, const N: usize = WORD_SIZE> {
+    script: Vec<&'w Word<N>>,
+}
+
+impl<'w, const N: usize> ScriptedGuesser<'w, N> {
+    pub fn new(script: &[&'w Word<N>]) -> Self {
+        Self {
+            script: script.to_vec(),
+        }
+    }
+}
+
+impl<'w, const N: usize> Guesser<'w, N> for ScriptedGuesser<'w, N> {
+    fn guess(&mut self, past_guesses: &[Guess<'w, N>]) -> &'w Word<N> {
+        self.script
+            .get(past_guesses.len())
+            .unwrap_or_else(|| panic!("The script has no guess {}", past_guesses.len() + 1))
+    }
+
+    fn name(&self) -> &'static str {
+        "scripted"
+    }
+}
+
+/// Wraps any guesser so that its first guess is always `opener`, and every later guess is up to
+/// the wrapped guesser. This makes games faster when the guesser would compute the same opener
+/// every time, and lets openers be compared against each other with the same guesser.
+pub struct WithOpener<'w, G, const N: usize = WORD_SIZE> {
+    opener: &'w Word<N>,
+    inner: G,
+}
+
+impl<'w, G: Guesser<'w, N>, const N: usize> WithOpener<'w, G, N> {
+    /// Fails with the error that guessing `opener` would have caused if it's not in `dictionary`.
+    pub fn new(
+        opener: &'w Word<N>,
+        dictionary: &Dictionary<'w, N>,
+        inner: G,
+    ) -> Result<Self, PlayError<N>> {
+        if !dictionary.contains(opener) {
+            return Err(PlayError::IllegalGuess {
+                attempt: 1,
+                word: *opener,
+            });
+        }
+
+        Ok(Self { opener, inner })
+    }
+}
+
+impl<'w, G: Guesser<'w, N>, const N: usize> Guesser<'w, N> for WithOpener<'w, G, N> {
+    fn guess(&mut self, past_guesses: &[Guess<'w, N>]) -> &'w Word<N> {
+        if past_guesses.is_empty() {
+            self.opener
+        } else {
+            self.inner.guess(past_guesses)
+        }
+    }
+
+    fn guess_with_context(
+        &mut self,
+        past_guesses: &[Guess<'w, N>],
+        context: &GameContext,
+    ) -> &'w Word<N> {
+        if past_guesses.is_empty() {
+            self.opener
+        } else {
+            self.inner.guess_with_context(past_guesses, context)
+        }
+    }
+
+    /// Whatever the inner guesser says, which knows nothing about the opener until its first
+    /// guess of the game.
+    fn last_expected_information(&self) -> Option<f64> {
+        self.inner.last_expected_information()
+    }
+
+    /// The opener doesn't change how the guesser plays otherwise, so it keeps its name.
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+
+    fn reset(&mut self) {
+        self.inner.reset()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    macro_rules! mask {
+        (C) => {crate::Correctness::Correct};
+        (M) => {crate::Correctness::Misplaced};
+        (W) => {crate::Correctness::Wrong};
+        ($($c:tt)+) => {[$(mask!($c)),+]}
+    }
+
+    mod play_wordle {
+        use crate::algorithms::Unoptimized;
+        use crate::{
+            Correctness, GameMode, Guess, Guesser, PlayError, ScriptedGuesser, Strictness, Word,
+            Wordle, WordleError,
+        };
+
+        macro_rules! guesser {
+            ($func:expr) => {
+                ($func) as fn(past_guesses: &[Guess]) -> &'static Word
+            };
+        }
+
+        const DICTIONARY: &str = include_str!("../dictionary.txt");
+
+        #[test]
+        fn guess_first_time_correctly() {
+            let wordle = Wordle::from_str_list(DICTIONARY);
+
+            assert_eq!(
+                wordle.play(b"moved", guesser!(|_past: &[Guess]| b"moved")),
+                Some(1)
+            );
+        }
+
+        #[test]
+        fn guess_third_time_correctly() {
+            let wordle = Wordle::from_str_list(DICTIONARY);
+
+            assert_eq!(
+                wordle.play(
+                    b"moved",
+                    guesser!(|past: &[Guess]| if past.len() == 2 { b"moved" } else { b"which" })
+                ),
+                Some(3)
+            );
+        }
+
+        #[test]
+        fn dont_guess_correctly() {
+            let wordle = Wordle::from_str_list(DICTIONARY);
+
+            pub struct DoesNotGuessCorrectly;
+
+            impl Guesser<'static> for DoesNotGuessCorrectly {
+                fn guess(&mut self, _past_guesses: &[Guess]) -> &'static Word {
+                    b"which"
+                }
+            }
+
+            assert_eq!(wordle.play(b"moved", DoesNotGuessCorrectly {}), None);
+        }
+
+        #[test]
+        fn recorded_play_keeps_every_guess() {
+            let wordle = Wordle::from_str_list(DICTIONARY);
+
+            let record = wordle.play_recorded(
+                b"moved",
+                guesser!(|past: &[Guess]| if past.len() == 2 { b"moved" } else { b"which" }),
+            );
+
+            assert_eq!(record.answer, b"moved");
+            assert_eq!(record.outcome, Some(3));
+            assert_eq!(
+                record
+                    .guesses
+                    .iter()
+                    .map(|guess| guess.word)
+                    .collect::<Vec<_>>(),
+                [b"which", b"which", b"moved"]
+            );
+            assert_eq!(record.to_emoji(true), "⬛⬛⬛⬛⬛\n⬛⬛⬛⬛⬛\n🟩🟩🟩🟩🟩");
+        }
+
+        #[test]
+        fn repeating_a_rejected_word_is_only_legal_in_normal_mode() {
+            let wordle = Wordle::from_str_list(DICTIONARY);
+            let repeats_which = ScriptedGuesser::new(&[b"which", b"which", b"moved"]);
+
+            assert_eq!(
+                wordle.play_with_mode(b"moved", repeats_which.clone(), GameMode::Normal),
+                Ok(Some(3))
+            );
+            assert_eq!(
+                wordle.play_with_mode(b"moved", repeats_which, GameMode::Hard(Strictness::Strict)),
+                Err(PlayError::HardModeViolation {
+                    attempt: 2,
+                    word: *b"which"
+                })
+            );
+        }
+
+        #[test]
+        fn loose_hard_mode_lets_gray_letters_be_reused() {
+            let wordle = Wordle::from_str_list(DICTIONARY);
+            // Every letter of "which" is gray, so it has nothing to keep, but also can't be the
+            // answer. "mover" then has to keep its four greens, which "moved" does.
+            let guesser = ScriptedGuesser::new(&[b"which", b"which", b"mover", b"moved"]);
+
+            assert_eq!(
+                wordle.play_with_mode(b"moved", guesser.clone(), GameMode::Hard(Strictness::Loose)),
+                Ok(Some(4))
+            );
+            assert_eq!(
+                wordle.play_with_mode(b"moved", guesser, GameMode::Hard(Strictness::Strict)),
+                Err(PlayError::HardModeViolation {
+                    attempt: 2,
+                    word: *b"which"
+                })
+            );
+
+            let after_mover = ScriptedGuesser::new(&[b"mover", b"maple"]);
+            assert_eq!(
+                wordle.play_with_mode(b"moved", after_mover, GameMode::Hard(Strictness::Loose)),
+                Err(PlayError::HardModeViolation {
+                    attempt: 2,
+                    word: *b"maple"
+                })
+            );
+        }
+
+        #[test]
+        fn compatible_guesses_are_legal_in_hard_mode() {
+            let wordle = Wordle::from_str_list(DICTIONARY);
+
+            assert_eq!(
+                wordle.play_with_mode(
+                    b"moved",
+                    ScriptedGuesser::new(&[b"mover", b"moved"]),
+                    GameMode::Hard(Strictness::Strict)
+                ),
+                Ok(Some(2))
+            );
+        }
+
+        #[test]
+        fn guess_allows_only_compatible_candidates() {
+            let guess = Guess {
+                word: b"mover",
+                mask: Correctness::check(b"moved", b"mover"),
+            };
+
+            assert!(guess.allows(b"moved"));
+            assert!(guess.allows(b"moves"));
+            assert!(!guess.allows(b"mover"));
+            assert!(!guess.allows(b"which"));
+        }
+
+        #[test]
+        fn loose_guesses_allow_what_strict_ones_dont() {
+            // "brace" keeps the green "r" of "crane" against "tread", and its "a" and "e", but
+            // also the gray "c", and puts the "a" and "e" back where they were yellow.
+            let guess = Guess::new(b"tread", b"crane");
+
+            assert!(guess.allows_with(b"brace", Strictness::Loose));
+            assert!(!guess.allows_with(b"brace", Strictness::Strict));
+            assert!(!guess.allows(b"brace"));
+            assert!(!guess.allows_with(b"fried", Strictness::Loose));
+            assert!(guess.allows_with(b"tread", Strictness::Strict));
+        }
+
+        #[test]
+        fn guess_limit_decides_the_loss() {
+            let wordle = Wordle::from_str_list(DICTIONARY);
+            let needs_seven = ScriptedGuesser::new(&[
+                b"which", b"which", b"which", b"which", b"which", b"which", b"moved",
+            ]);
+
+            assert_eq!(
+                wordle.play_with_limit(b"moved", needs_seven.clone(), 6),
+                None
+            );
+            assert_eq!(
+                wordle.play_with_limit(b"moved", needs_seven.clone(), 7),
+                Some(7)
+            );
+            assert_eq!(
+                wordle.play_with_limit(b"moved", needs_seven.clone(), 10),
+                Some(7)
+            );
+            assert_eq!(
+                wordle.play_with_limit(b"moved", needs_seven, usize::MAX),
+                Some(7)
+            );
+        }
+
+        #[test]
+        fn illegal_guess_is_an_error() {
+            let wordle = Wordle::from_str_list(DICTIONARY);
+
+            assert_eq!(
+                wordle.try_play(b"moved", ScriptedGuesser::new(&[b"which", b"zzzzz"])),
+                Err(PlayError::IllegalGuess {
+                    attempt: 2,
+                    word: *b"zzzzz"
+                })
+            );
+        }
+
+        #[test]
+        fn play_returning_hands_back_the_guesser() {
+            let words: [&Word; 4] = [b"hello", b"world", b"moved", b"which"];
+            let wordle = Wordle::new(words);
+            let guesser = Unoptimized::new(
+                wordle.get_dictionary(),
+                wordle.get_possible_answers().clone(),
+            )
+            .with_profiling();
+
+            let (outcome, guesser) = wordle.play_returning(b"moved", guesser);
+            assert_eq!(Some(guesser.timings().len()), outcome);
+        }
+
+        #[test]
+        fn the_observer_sees_every_guess_as_it_is_made() {
+            let wordle = Wordle::new([b"hello", b"world", b"moved", b"which"]);
+            let mut seen = Vec::new();
+
+            let outcome = wordle.play_with_observer(
+                b"moved",
+                ScriptedGuesser::new(&[b"which", b"world", b"moved"]),
+                |guess, attempt| seen.push((attempt, guess.to_string())),
+            );
+
+            assert_eq!(outcome, Some(3));
+            assert_eq!(
+                seen,
+                [
+                    (1, String::from("which WWWWW")),
+                    (2, String::from("world WCWWC")),
+                    (3, String::from("moved CCCCC")),
+                ]
+            );
+        }
+
+        #[test]
+        fn empty_dictionary_is_an_error() {
+            assert_eq!(
+                Wordle::try_new(std::iter::empty::<&Word>()).err(),
+                Some(WordleError::EmptyDictionary)
+            );
+            assert!(Wordle::try_new([b"hello"]).is_ok());
+        }
+
+        #[test]
+        fn answers_that_are_not_possible_answers_are_errors() {
+            // "crane" can be guessed but isn't a possible answer, so once "hello" is ruled out
+            // there would be nothing left that could be the answer.
+            let wordle = Wordle::with_answers([b"crane", b"hello"], [(b"hello", 1.0)].into());
+
+            // The game is turned down before the guesser is asked for anything, which would panic
+            // with an empty script.
+            assert_eq!(
+                wordle.try_play(b"crane", ScriptedGuesser::new(&[])),
+                Err(PlayError::UnknownAnswer { word: *b"crane" })
+            );
+            assert_eq!(
+                wordle.try_play(b"hello", ScriptedGuesser::new(&[b"hello"])),
+                Ok(Some(1))
+            );
+        }
+
+        #[test]
+        #[should_panic(expected = "not in the dictionary")]
+        fn illegal_guess_panics_in_play() {
+            let wordle = Wordle::from_str_list(DICTIONARY);
+
+            wordle.play(b"moved", guesser!(|_past: &[Guess]| b"zzzzz"));
+        }
+
+        #[test]
+        #[should_panic(expected = "The script has no guess 2")]
+        fn scripts_can_run_out() {
+            let wordle: Wordle = Wordle::new([b"hello", b"world"]);
+
+            wordle.play(b"world", ScriptedGuesser::new(&[b"hello"]));
+        }
+
+        #[test]
+        fn answer_missing_from_the_dictionary() {
+            let wordle: Wordle = Wordle::new([b"hello", b"world"]);
+            let remaining = wordle.get_possible_answers().clone();
+
+            // "crane" is a fine word, but not one this game knows about.
+            assert_eq!(
+                wordle.try_play(
+                    b"crane",
+                    Unoptimized::new(wordle.get_dictionary(), remaining.clone())
+                ),
+                Err(PlayError::UnknownAnswer { word: *b"crane" })
+            );
+            assert_eq!(
+                wordle
+                    .try_play(
+                        b"world",
+                        Unoptimized::new(wordle.get_dictionary(), remaining)
+                    )
+                    .map(|outcome| outcome.is_some()),
+                Ok(true)
+            );
+        }
+    }
+
+    mod borrowed_words {
+        use crate::algorithms::Unoptimized;
+        use crate::{parse_transcript, Guess, RepresentableAsWord, Word, Wordle};
+
+        #[test]
+        fn a_game_can_borrow_its_words_from_a_local_buffer() {
+            // Words read at runtime, which live only as long as this test instead of forever.
+            let text = String::from("hello\nworld\nmoved\nwhich\njello");
+            let words = Vec::from_iter(text.lines().map(|line| *line.as_word()));
+
+            let wordle = Wordle::new(words.iter());
+            let answer: &Word = &words[2];
+            assert_eq!(
+                wordle.play(answer, Unoptimized::from_wordle(&wordle)),
+                Some(2)
+            );
+
+            let transcript = format!("{}\n", Guess::new(answer, &words[0]));
+            let past = parse_transcript(&transcript).unwrap();
+            assert_eq!(
+                Guess::filter_candidates(&past, wordle.get_possible_answers().keys()),
+                [answer]
+            );
+        }
+    }
+
+    mod builder {
+        use crate::{LoadWarning, Wordle, WordleBuildError};
+
+        const COUNTS: &str = "hello 10\nworld 5\nwhich 20\n";
+
+        #[test]
+        fn counts_are_the_dictionary_by_default() {
+            let (wordle, counts) = Wordle::builder(COUNTS).build().unwrap();
+
+            assert_eq!(wordle.get_dictionary().len(), 3);
+            assert_eq!(&counts, wordle.get_possible_answers());
+            assert_eq!(counts[b"which"], 20.0);
+        }
+
+        #[test]
+        fn answers_narrow_down_the_counts() {
+            let (wordle, counts) = Wordle::builder(COUNTS)
+                .dictionary("hello\nworld\nwhich\n")
+                .answers("world\n")
+                .build()
+                .unwrap();
+
+            assert_eq!(wordle.get_dictionary().len(), 3);
+            assert_eq!(counts.len(), 1);
+            assert_eq!(counts[b"world"], 5.0);
+            // The game keeps the counts of every word, not only of its answers.
+            assert_eq!(wordle.get_counts().map(|counts| counts.len()), Some(3));
+            assert_eq!(&wordle.counted_answers(), &counts);
+        }
+
+        #[test]
+        fn lists_have_to_agree() {
+            assert_eq!(
+                Wordle::builder(COUNTS)
+                    .dictionary("hello\nworld\nwhich\nmoved\n")
+                    .build()
+                    .err(),
+                Some(WordleBuildError::MissingCount(b"moved"))
+            );
+            assert_eq!(
+                Wordle::builder(COUNTS).dictionary("hello\n").build().err(),
+                Some(WordleBuildError::NotInDictionary(b"which"))
+            );
+            assert_eq!(
+                Wordle::builder(COUNTS).answers("moved\n").build().err(),
+                Some(WordleBuildError::MissingCount(b"moved"))
+            );
+        }
+
+        #[test]
+        fn bad_lines_fail_the_build() {
+            assert_eq!(
+                Wordle::builder("hello 10\nworld\n").build().err(),
+                Some(WordleBuildError::InvalidLine(LoadWarning::MissingCount {
+                    line: 2
+                }))
+            );
+        }
+    }
+
+    mod from_str_list {
+        use crate::Wordle;
+
+        #[test]
+        fn every_good_line_is_a_word() {
+            let wordle = Wordle::from_str_list(
+                "crane
+                 # a comment
+                 slate
+
+                 toolong
+                 crane
+                 trace",
+            );
+
+            assert_eq!(wordle.get_dictionary().len(), 3);
+            assert!(wordle.get_dictionary().contains(b"slate"));
+            assert!(wordle.get_possible_answers().contains_key(b"trace"));
+            assert!(!wordle.get_dictionary().contains(b"toolo"));
+        }
+    }
+
+    mod score_distribution {
+        use crate::{
+            Guess, Guesser, OutputFormat, ScoreDistribution, ScriptedGuesser, Word, Wordle,
+            TRIES_BEFORE_LOSS,
+        };
+
+        #[test]
+        fn counts_wins_and_losses() {
+            let distribution =
+                ScoreDistribution::from_iter([Some(3), Some(4), Some(3), None, Some(7)]);
+
+            assert_eq!(distribution.counts[3], 2);
+            assert_eq!(distribution.counts[4], 1);
+            assert_eq!(distribution.counts[7], 1);
+            assert_eq!(distribution.losses, 1);
+            assert_eq!(distribution.games(), 5);
+            assert_eq!(distribution.mean(), Some(17.0 / 4.0));
+            assert_eq!(distribution.win_rate(6), Some(3.0 / 5.0));
+        }
+
+        #[test]
+        fn lost_games_are_counted_apart_from_the_mean() {
+            // "world" is guessed every time, so the game for "hello" is lost.
+            let wordle = Wordle::new([b"hello", b"world"]);
+            let distribution = wordle.evaluate([b"hello", b"world"], || {
+                ScriptedGuesser::new(&[b"world"; TRIES_BEFORE_LOSS])
+            });
+
+            assert_eq!(distribution.losses, 1);
+            assert_eq!(distribution.mean(), Some(1.0));
+            assert_eq!(distribution.win_rate(6), Some(0.5));
+            assert!(distribution.to_string().contains("lost | "));
+        }
+
+        #[test]
+        fn mean_without_wins() {
+            let distribution = ScoreDistribution::from_iter([None, None]);
+
+            assert_eq!(distribution.mean(), None);
+            assert_eq!(distribution.win_rate(6), Some(0.0));
+        }
+
+        #[test]
+        fn win_rate_without_games() {
+            let distribution = ScoreDistribution::default();
+
+            assert_eq!(distribution.win_rate(6), None);
+            assert!(distribution.to_string().ends_with("no wins, no games"));
+        }
+
+        #[test]
+        fn displays_a_bar_per_guess_count() {
+            let distribution = ScoreDistribution::from_iter([Some(2), Some(3), Some(3), None]);
+
+            assert_eq!(
+                distribution.to_string(),
+                [
+                    "   1 |  0",
+                    "   2 | ######################### 1",
+                    "   3 | ################################################## 2",
+                    "lost | ######################### 1",
+                    "mean 2.6667, 75.00% won within 6",
+                ]
+                .join("\n")
+            );
+        }
+
+        /// Guesses the words in order, so the nth word is found on the nth guess.
+        fn in_order(past: &[Guess]) -> &'static Word {
+            [b"hello", b"world", b"which"][past.len()]
+        }
+
+        #[test]
+        fn evaluate_writes_csv_rows() {
+            let wordle = Wordle::new([b"hello", b"world", b"which"]);
+            let mut output = Vec::new();
+
+            let distribution = wordle
+                .evaluate_to_writer(
+                    [b"hello", b"world"],
+                    || in_order as fn(&[Guess]) -> &'static Word,
+                    &mut output,
+                    OutputFormat::Csv,
+                    true,
+                )
+                .unwrap();
+
+            assert_eq!(
+                String::from_utf8(output).unwrap(),
+                "guesser,answer,guesses,sequence\nfunction,hello,1,hello\nfunction,world,2,hello world\n"
+            );
+            assert_eq!(distribution.mean(), Some(1.5));
+        }
+
+        #[test]
+        fn evaluate_writes_a_json_array() {
+            let wordle = Wordle::new([b"hello", b"world", b"which"]);
+            let mut output = Vec::new();
+
+            wordle
+                .evaluate_to_writer(
+                    [b"hello", b"world"],
+                    || in_order as fn(&[Guess]) -> &'static Word,
+                    &mut output,
+                    OutputFormat::Json,
+                    false,
+                )
+                .unwrap();
+
+            assert_eq!(
+                String::from_utf8(output).unwrap(),
+                "[\n{\"guesser\":\"function\",\"answer\":\"hello\",\"guesses\":1},\n\
+                 {\"guesser\":\"function\",\"answer\":\"world\",\"guesses\":2}\n]\n"
+            );
+        }
+
+        /// Guesses a word with a comma and a quote in it, under a name with a backslash too, all of
+        /// which have to be escaped in both formats.
+        struct Quoted;
+
+        impl Guesser<'static> for Quoted {
+            fn guess(&mut self, _past_guesses: &[Guess]) -> &'static Word {
+                b"a,\"b\""
+            }
+
+            fn name(&self) -> &'static str {
+                "say \"hi\", \\o/"
+            }
+        }
+
+        #[test]
+        fn names_and_words_are_escaped() {
+            let wordle = Wordle::new([b"a,\"b\""]);
+            let write = |format| {
+                let mut output = Vec::new();
+                wordle
+                    .evaluate_to_writer([b"a,\"b\""], || Quoted, &mut output, format, true)
+                    .unwrap();
+                String::from_utf8(output).unwrap()
+            };
+
+            assert_eq!(
+                write(OutputFormat::Csv),
+                "guesser,answer,guesses,sequence\n\
+                 \"say \"\"hi\"\", \\o/\",\"a,\"\"b\"\"\",1,\"a,\"\"b\"\"\"\n"
+            );
+            assert_eq!(
+                write(OutputFormat::Json),
+                "[\n{\"guesser\":\"say \\\"hi\\\", \\\\o/\",\"answer\":\"a,\\\"b\\\"\",\
+                 \"guesses\":1,\"sequence\":[\"a,\\\"b\\\"\"]}\n]\n"
+            );
+        }
+
+        #[test]
+        fn evaluate_plays_every_answer() {
+            let words: [&'static Word; 3] = [b"hello", b"world", b"which"];
+            let wordle = Wordle::new(words);
+
+            let distribution = wordle.evaluate(words, || in_order as fn(&[Guess]) -> &'static Word);
+
+            assert_eq!(distribution.counts[1..4], [1, 1, 1]);
+            assert_eq!(distribution.losses, 0);
+            assert_eq!(distribution.mean(), Some(2.0));
+        }
+
+        #[test]
+        fn the_hardest_answers_took_the_most_guesses() {
+            let words: [&'static Word; 3] = [b"hello", b"world", b"which"];
+            let wordle = Wordle::new(words);
+
+            let hardest =
+                wordle.hardest_answers(words, || in_order as fn(&[Guess]) -> &'static Word, 2);
+
+            assert_eq!(hardest, [(b"which", Some(3)), (b"world", Some(2))]);
+        }
+
+        #[test]
+        fn lost_games_are_the_hardest() {
+            let words: [&'static Word; 3] = [b"hello", b"world", b"which"];
+            let wordle = Wordle::new(words);
+
+            // Only ever guessing "world" wins one game on the first guess and loses the others.
+            let hardest = wordle.hardest_answers(
+                words,
+                || (|_: &[Guess]| b"world") as fn(&[Guess]) -> &'static Word,
+                3,
+            );
+
+            assert_eq!(
+                hardest,
+                [(b"hello", None), (b"which", None), (b"world", Some(1))]
+            );
+        }
+
+        /// Like `in_order`, but keeps its own place in the list instead of going by the past
+        /// guesses, so a guesser that was used before starts off where the last game ended.
+        struct Naive {
+            next: usize,
+        }
+
+        impl Guesser<'static> for Naive {
+            fn guess(&mut self, _past_guesses: &[Guess]) -> &'static Word {
+                self.next += 1;
+                [b"hello", b"world", b"which"][self.next - 1]
+            }
+
+            fn name(&self) -> &'static str {
+                "naive"
+            }
+        }
+
+        #[test]
+        fn guessers_are_named() {
+            assert_eq!(Naive { next: 0 }.name(), "naive");
+            assert_eq!(ScriptedGuesser::<5>::new(&[]).name(), "scripted");
+
+            // Without a name of its own, a guesser goes by the name of its type.
+            struct Unnamed;
+            impl Guesser<'static> for Unnamed {
+                fn guess(&mut self, _past_guesses: &[Guess]) -> &'static Word {
+                    b"hello"
+                }
+            }
+            assert!(Unnamed.name().ends_with("Unnamed"));
+        }
+
+        #[test]
+        fn evaluate_makes_a_fresh_guesser_per_answer() {
+            let wordle = Wordle::new([b"hello", b"world", b"which"]);
+            let mut made = 0;
+
+            // A reused `Naive` would pick up at "world" after finding "hello", and find "world" in
+            // one guess instead of two.
+            let distribution = wordle.evaluate([b"hello", b"world"], || {
+                made += 1;
+                Naive { next: 0 }
+            });
+
+            assert_eq!(made, 2);
+            assert_eq!(distribution.counts[1..3], [1, 1]);
+            assert_eq!(distribution.losses, 0);
+        }
+    }
+
+    mod adversarial {
+        use crate::{DictionaryWithCounts, Guess, PlayError, ScriptedGuesser, Word, Wordle};
+
+        const ANSWERS: [&Word; 4] = [b"hello", b"jello", b"cello", b"mello"];
+
+        #[test]
+        fn guessing_the_answers_one_by_one_takes_every_guess() {
+            let wordle = Wordle::new(ANSWERS);
+
+            // Every guess leaves the rest of the words in one group, until it's down to "cello"
+            // and "mello", where the win is dodged once more.
+            assert_eq!(
+                wordle.play_adversarial(ScriptedGuesser::new(&[
+                    b"hello", b"jello", b"cello", b"mello"
+                ])),
+                Some(4)
+            );
+        }
+
+        #[test]
+        fn a_guess_that_splits_everything_wins_next() {
+            let wordle = Wordle::with_answers(
+                [b"chjmx"],
+                ANSWERS.into_iter().map(|word| (word, 1.0)).collect(),
+            );
+            let after_chjmx = |past: &[Guess]| {
+                if past.is_empty() {
+                    b"chjmx"
+                } else {
+                    Guess::filter_candidates(past, &ANSWERS)[0]
+                }
+            };
+
+            assert_eq!(
+                wordle.play_adversarial(after_chjmx as fn(&[Guess]) -> &'static Word),
+                Some(2)
+            );
+        }
+
+        #[test]
+        fn never_guessing_the_last_word_loses() {
+            let wordle = Wordle::new(ANSWERS);
+
+            assert_eq!(
+                wordle.play_adversarial((|_: &[Guess]| b"hello") as fn(&[Guess]) -> &'static Word),
+                None
+            );
+        }
+
+        #[test]
+        fn no_possible_answers_is_an_error() {
+            let wordle = Wordle::with_answers(ANSWERS, DictionaryWithCounts::new());
+
+            assert_eq!(
+                wordle.try_play_adversarial(ScriptedGuesser::new(&[b"hello"])),
+                Err(PlayError::NoPossibleAnswers { attempt: 1 })
+            );
+        }
+    }
+
+    mod information {
+        use crate::algorithms::Unoptimized;
+        use crate::{realized_information, DictionaryWithCounts, ScriptedGuesser, Word, Wordle};
+
+        #[test]
+        fn realized_information_counts_halvings() {
+            assert_eq!(realized_information(8, 2), 2.0);
+            assert_eq!(realized_information(2, 1), 1.0);
+            assert_eq!(realized_information(5, 5), 0.0);
+        }
+
+        #[test]
+        fn ruling_everything_out_is_infinite_information() {
+            assert_eq!(realized_information(3, 0), f64::INFINITY);
+            assert_eq!(realized_information(0, 0), f64::INFINITY);
+        }
+
+        #[test]
+        fn records_expected_and_realized_bits() {
+            let words: [&Word; 4] = [b"hello", b"jello", b"world", b"moved"];
+            let wordle = Wordle::new(words);
+            let record = wordle.play_recorded(
+                b"jello",
+                ScriptedGuesser::new(&[b"world", b"hello", b"jello"]),
+            );
+
+            let information = record.information(wordle.get_possible_answers());
+            assert_eq!(information.len(), 3);
+
+            // A script doesn't expect anything of its guesses.
+            assert!(information.iter().all(|guess| guess.expected.is_none()));
+
+            // "world" splits the four words into {world}, {moved} and {hello, jello}, and leaving
+            // two of them is worth a bit. Then "hello" leaves one, which is another bit.
+            assert_eq!(information[0].realized, 1.0);
+            assert_eq!(information[1].realized, 1.0);
+
+            // With one word left, there is nothing left to find out.
+            assert_eq!(information[2].realized, 0.0);
+        }
+
+        #[test]
+        fn expected_bits_are_what_the_guesser_expected() {
+            let words: [&Word; 4] = [b"hello", b"jello", b"world", b"moved"];
+            let wordle = Wordle::new(words);
+            let guesser = Unoptimized::new(
+                wordle.get_dictionary(),
+                DictionaryWithCounts::from([(b"hello", 1.0), (b"jello", 3.0)]),
+            );
+            let record = wordle.play_recorded(b"jello", guesser);
+
+            // With "jello" counted three times over, `Unoptimized` guesses it right away. Telling
+            // it apart from "hello" is worth the entropy of 3 to 1 odds to the guesser, while the
+            // game counts all four of its answers the same, so finding one of them is two bits.
+            let information = record.information(wordle.get_possible_answers());
+            let entropy = -(0.25 * 0.25f64.log2() + 0.75 * 0.75f64.log2());
+            assert_eq!(record.outcome, Some(1));
+            assert!((information[0].expected.unwrap() - entropy).abs() < 1e-12);
+            assert_eq!(information[0].realized, 2.0);
+        }
+
+        #[test]
+        fn realized_bits_are_over_the_possible_answers_only() {
+            let wordle = Wordle::with_answers(
+                [b"chjmx"],
+                DictionaryWithCounts::from([(b"hello", 1.0), (b"jello", 1.0)]),
+            );
+            let record =
+                wordle.play_recorded(b"jello", ScriptedGuesser::new(&[b"chjmx", b"jello"]));
+
+            let information = record.information(wordle.get_possible_answers());
+            assert_eq!(information[0].expected, None);
+            assert_eq!(information[0].realized, 1.0);
+        }
+    }
+
+    mod answer_for_index {
+        use crate::{RepresentableAsWord, Wordle};
+
+        const GAMES: &str = include_str!("../answers.txt");
+
+        #[test]
+        fn index_0_is_the_first_answer() {
+            let answers = Vec::from_iter(GAMES.lines().map(|answer| answer.as_word()));
+            let wordle = Wordle::new(answers.iter().copied());
+
+            assert_eq!(wordle.answer_for_index(0, &answers), b"cigar");
+            assert_eq!(wordle.answer_for_index(1, &answers), b"rebut");
+            assert_eq!(wordle.answer_for_index(answers.len(), &answers), b"cigar");
+        }
+    }
+
+    mod multi_wordle {
+        use crate::{Guess, MultiGuesser, MultiWordle, PlayError, Word, Wordle};
+        use std::cell::RefCell;
+
+        /// Guesses `words` in order, and notes down how many guesses each board had every turn.
+        struct Recorder<'a> {
+            words: &'a [&'static Word],
+            lengths: &'a RefCell<Vec<Vec<usize>>>,
+        }
+
+        impl<'a> MultiGuesser<'static> for Recorder<'a> {
+            fn guess(&mut self, boards: &[Vec<Guess>]) -> &'static Word {
+                let mut lengths = self.lengths.borrow_mut();
+                lengths.push(Vec::from_iter(boards.iter().map(|board| board.len())));
+
+                self.words[lengths.len() - 1]
+            }
+        }
+
+        #[test]
+        fn solved_boards_get_no_more_guesses() {
+            let game = MultiWordle::new(Wordle::new([b"hello", b"world", b"moved"]));
+            let lengths = RefCell::new(Vec::new());
+            let guesser = Recorder {
+                words: &[b"world", b"moved", b"hello"],
+                lengths: &lengths,
+            };
+
+            assert_eq!(game.play_multi(&[b"hello", b"world"], guesser), Some(3));
+            assert_eq!(lengths.into_inner(), [vec![0, 0], vec![1, 1], vec![2, 1]]);
+        }
+
+        #[test]
+        fn unknown_answers_and_illegal_guesses_are_errors() {
+            let game = MultiWordle::new(Wordle::new([b"hello", b"world"]));
+            let lengths = RefCell::new(Vec::new());
+            let guesser = || Recorder {
+                words: &[b"which"],
+                lengths: &lengths,
+            };
+
+            assert_eq!(
+                game.try_play_multi(&[b"hello", b"moved"], guesser()),
+                Err(PlayError::UnknownAnswer { word: *b"moved" })
+            );
+            assert_eq!(
+                game.try_play_multi(&[b"hello", b"world"], guesser()),
+                Err(PlayError::IllegalGuess {
+                    attempt: 1,
+                    word: *b"which"
+                })
+            );
+        }
+    }
+
+    mod roget_error {
+        use crate::{
+            Correctness, PlayError, RepresentableAsWord, RogetError, ScriptedGuesser, WordError,
+            Wordle, WordleBuildError,
+        };
+        use std::error::Error;
+
+        /// Reads a game from a line like "hello crane WMCWW", and plays it with its only guess.
+        fn replay(line: &'static str) -> Result<Option<usize>, RogetError> {
+            let words = Vec::from_iter(line.split(' '));
+            let answer = words[0].try_as_word()?;
+            let guess = words[1].try_as_word()?;
+            Correctness::parse_mask(words[2])?;
+
+            let wordle = Wordle::try_new([answer])?;
+            Ok(wordle.try_play(answer, ScriptedGuesser::new(&[guess]))?)
+        }
+
+        #[test]
+        fn every_error_converts_with_a_question_mark() {
+            assert_eq!(replay("hello hello CCCCC"), Ok(Some(1)));
+            assert_eq!(
+                replay("hell crane CCCCC"),
+                Err(RogetError::ParseWord(WordError::TooShort { len: 4 }))
+            );
+            assert!(matches!(
+                replay("hello crane CCCQC"),
+                Err(RogetError::ParseMask(_))
+            ));
+            assert!(matches!(
+                replay("hello crane CCCCC"),
+                Err(RogetError::IllegalGuess(PlayError::IllegalGuess {
+                    attempt: 1,
+                    ..
+                }))
+            ));
+        }
+
+        #[test]
+        fn running_out_of_answers_is_an_inconsistent_mask() {
+            let error = RogetError::from(PlayError::<5>::NoPossibleAnswers { attempt: 3 });
+
+            assert_eq!(error, RogetError::MaskInconsistent { attempt: 3 });
+            assert_eq!(
+                error.to_string(),
+                "no possible answer is left to make guess 3 from"
+            );
+        }
+
+        #[test]
+        fn word_list_errors_keep_their_line() {
+            let error = Wordle::builder("hello 12\nworld x\n")
+                .build()
+                .map(|_| ())
+                .unwrap_err();
+            assert_eq!(
+                RogetError::<5>::from(error),
+                RogetError::ParseCount { line: 2 }
+            );
+        }
+
+        #[test]
+        fn sources_are_the_wrapped_errors() {
+            let error = RogetError::<5>::from(WordleBuildError::MissingCount(b"hello"));
+            assert_eq!(
+                error.source().map(|source| source.to_string()),
+                Some(String::from("hello has no count"))
+            );
+            assert!(RogetError::<5>::EmptyDictionary.source().is_none());
+        }
+    }
+
+    mod rank_openers {
+        use crate::{DictionaryWithCounts, Word, Wordle};
+
+        #[test]
+        fn best_openers_come_first() {
+            let answers: [&Word; 4] = [b"hello", b"jello", b"cello", b"mello"];
+            let counts = DictionaryWithCounts::from_iter(answers.map(|word| (word, 1.0)));
+            let wordle = Wordle::with_answers([b"chjmx", b"crane"], counts.clone());
+
+            let openers = wordle.rank_openers(&counts, 3);
+            assert_eq!(openers.len(), 3);
+            // "chjmx" tells the four answers apart, which is two bits.
+            assert_eq!(openers[0], (b"chjmx", 2.0));
+            assert!(openers.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+
+            let openers = wordle.rank_openers(&counts, 10);
+            assert_eq!(openers.len(), wordle.get_dictionary().len());
+            assert!(openers.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+        }
+    }
+
+    mod render_board {
+        use crate::{render_board_styled, Guess, RenderStyle};
+
+        #[test]
+        fn without_color_guesses_are_written_with_their_masks() {
+            let guesses = [
+                Guess::new(b"moved", b"crane"),
+                Guess::new(b"moved", b"moved"),
+            ];
+
+            assert_eq!(
+                render_board_styled(&guesses, RenderStyle::Ascii),
+                "crane WWWWM\nmoved CCCCC\n"
+            );
+        }
+
+        #[test]
+        fn with_color_letters_get_the_background_of_their_mask() {
+            let board = render_board_styled(&[Guess::new(b"moved", b"dozen")], RenderStyle::Emoji);
+
+            assert!(board.starts_with("\x1b[30;43m D \x1b[0m\x1b[30;42m O \x1b[0m"));
+            assert!(board.ends_with("\x1b[30;100m N \x1b[0m\n"));
+        }
+
+        #[test]
+        fn colorblind_letters_are_orange_and_blue() {
+            let board = render_board_styled(
+                &[Guess::new(b"moved", b"dozen")],
+                RenderStyle::ColorblindEmoji,
+            );
+
+            assert!(board.starts_with("\x1b[30;44m D \x1b[0m\x1b[30;48;5;208m O \x1b[0m"));
+            assert!(board.ends_with("\x1b[30;100m N \x1b[0m\n"));
+        }
+    }
+
+    mod parse_transcript {
+        use crate::{
+            parse_transcript, Correctness, Guess, ParseMaskError, RepresentableAsWord,
+            TranscriptError, WordError,
+        };
+
+        #[test]
+        fn every_line_is_a_guess_and_its_mask() {
+            let guesses = parse_transcript("crane 🟩⬛⬛⬛🟨\n\n  cleft CCCCC\n").unwrap();
+
+            assert_eq!(guesses.len(), 2);
+            assert_eq!(guesses[0].word, b"crane");
+            assert_eq!(guesses[0].mask, Correctness::from_letters("CWWWM").unwrap());
+            assert_eq!(guesses[1].word, b"cleft");
+            assert_eq!(guesses[1].mask, [Correctness::Correct; crate::WORD_SIZE]);
+            // The masks are what the answer would have given, so the transcript replays.
+            assert!(guesses.iter().all(|guess| guess.allows(b"cleft")));
+        }
+
+        #[test]
+        fn bad_lines_are_reported_with_their_number() {
+            assert_eq!(
+                parse_transcript("crane WWWWW\ncran WWWWW").unwrap_err(),
+                TranscriptError::Word {
+                    line: 2,
+                    error: WordError::TooShort { len: 4 }
+                }
+            );
+            assert_eq!(
+                parse_transcript("crane 🟨⬛⬛🟩").unwrap_err(),
+                TranscriptError::Mask {
+                    line: 1,
+                    error: ParseMaskError::WrongLength(4)
+                }
+            );
+            assert_eq!(
+                parse_transcript("\ncrane").unwrap_err(),
+                TranscriptError::Mask {
+                    line: 2,
+                    error: ParseMaskError::WrongLength(0)
+                }
+            );
+        }
+
+        #[test]
+        fn a_transcript_replays_into_the_same_guesses() {
+            let guesses = [
+                Guess::new(b"moved", b"crane"),
+                Guess::new(b"moved", b"dozen"),
+            ];
+            let transcript = guesses
+                .iter()
+                .map(|guess| format!("{}\n", guess))
+                .collect::<String>();
+
+            let parsed = parse_transcript(String::leak(transcript)).unwrap();
+            assert_eq!(
+                parsed
+                    .iter()
+                    .map(|guess| guess.word.as_word())
+                    .collect::<Vec<_>>(),
+                [b"crane", b"dozen"]
+            );
+            assert!(parsed.iter().zip(&guesses).all(|(a, b)| a.mask == b.mask));
+        }
+    }
+
+    mod letter_status {
+        use crate::{letter_status, Guess, LetterState};
+
+        fn state(states: &[LetterState; 26], letter: u8) -> LetterState {
+            states[usize::from(letter - b'a')]
+        }
+
+        #[test]
+        fn a_later_green_places_a_gray_letter() {
+            // "ebony" has one "e", so only the first "e" of "levee" is yellow, and the other two
+            // are gray.
+            let levee = [Guess::new(b"ebony", b"levee")];
+            assert_eq!(state(&letter_status(&levee), b'e'), LetterState::Present);
+
+            let past = [
+                Guess::new(b"ebony", b"levee"),
+                Guess::new(b"ebony", b"eerie"),
+            ];
+            let states = letter_status(&past);
+            assert_eq!(state(&states, b'e'), LetterState::Placed);
+            assert_eq!(state(&states, b'l'), LetterState::Absent);
+            assert_eq!(state(&states, b'o'), LetterState::Unknown);
+
+            // However the guesses are ordered.
+            let [levee, eerie] = past;
+            assert_eq!(letter_status(&[eerie, levee]), states);
+        }
+
+        #[test]
+        fn gray_copies_of_yellow_letters_are_present() {
+            let states = letter_status(&[Guess::new(b"abbey", b"kebab")]);
+
+            assert_eq!(state(&states, b'a'), LetterState::Present);
+            assert_eq!(state(&states, b'b'), LetterState::Placed);
+            assert_eq!(state(&states, b'e'), LetterState::Present);
+            assert_eq!(state(&states, b'k'), LetterState::Absent);
+        }
+
+        #[test]
+        fn nothing_is_known_before_the_first_guess() {
+            let past: [Guess; 0] = [];
+            assert_eq!(letter_status(&past), [LetterState::Unknown; 26]);
+        }
+    }
+
+    mod positional_frequencies {
+        use crate::{positional_frequencies, Dictionary, Word};
+
+        #[test]
+        fn counts_every_letter_in_its_spot() {
+            let dictionary =
+                Dictionary::from([b"crane", b"slate", b"crate"].map(|word| word as &Word));
+            let frequencies = positional_frequencies(&dictionary);
+            let count =
+                |position: usize, letter: u8| frequencies[position][usize::from(letter - b'a')];
+
+            assert_eq!(count(0, b'c'), 2);
+            assert_eq!(count(0, b's'), 1);
+            assert_eq!(count(2, b'a'), 3);
+            assert_eq!(count(3, b't'), 2);
+            assert_eq!(count(4, b'e'), 3);
+            assert_eq!(count(4, b'a'), 0);
+            assert_eq!(frequencies.map(|spot| spot.iter().sum::<u32>()), [3; 5]);
+        }
+
+        #[test]
+        fn other_bytes_are_not_counted() {
+            let words: [&Word; 2] = [b"b4ker", b"O'DEA"];
+            let frequencies = positional_frequencies(&words);
+
+            assert_eq!(frequencies[0][usize::from(b'b' - b'a')], 1);
+            assert_eq!(frequencies[1], [0; 26]);
+            assert_eq!(
+                frequencies.map(|spot| spot.iter().sum::<u32>()),
+                [1, 0, 1, 1, 1]
+            );
+        }
+    }
+
+    mod alphabet {
+        use crate::{
+            letter_status, letter_status_in, positional_frequencies, positional_frequencies_in,
+            Alphabet, AsciiLowercase, Guess, LetterState, Word,
+        };
+
+        /// The digits from '0' to '9', and '#', standing in for the symbols of a custom game.
+        struct Symbols;
+
+        impl Alphabet for Symbols {
+            fn size(&self) -> usize {
+                11
+            }
+
+            fn index(&self, letter: u8) -> Option<usize> {
+                match letter {
+                    b'0'..=b'9' => Some(usize::from(letter - b'0')),
+                    b'#' => Some(10),
+                    _ => None,
+                }
+            }
+        }
+
+        #[test]
+        fn ascii_lowercase_is_a_to_z() {
+            assert_eq!(AsciiLowercase.size(), 26);
+            assert_eq!(AsciiLowercase.index(b'a'), Some(0));
+            assert_eq!(AsciiLowercase.index(b'z'), Some(25));
+            assert_eq!(AsciiLowercase.index(b'A'), None);
+            assert_eq!(AsciiLowercase.index(b'4'), None);
+
+            let past = [Guess::new(b"abbey", b"kebab")];
+            assert_eq!(
+                letter_status_in(&past, &AsciiLowercase),
+                letter_status(&past)
+            );
+        }
+
+        #[test]
+        fn custom_alphabets_get_a_state_and_count_per_symbol() {
+            let past = [Guess::new(b"12#34", b"1#999")];
+            let states = letter_status_in(&past, &Symbols);
+
+            assert_eq!(states.len(), 11);
+            assert_eq!(states[1], LetterState::Placed);
+            assert_eq!(states[10], LetterState::Present);
+            assert_eq!(states[9], LetterState::Absent);
+            assert_eq!(states[2], LetterState::Unknown);
+
+            let words: [&Word; 3] = [b"12#34", b"1#999", b"abcde"];
+            let frequencies = positional_frequencies_in(&words, &Symbols);
+            assert_eq!(frequencies[0][1], 2);
+            assert_eq!(frequencies[1][10], 1);
+            assert_eq!(frequencies.map(|spot| spot.iter().sum::<u32>()), [2; 5]);
+            // The letters aren't in this alphabet, while the symbols aren't in the default one.
+            assert_eq!(positional_frequencies(&words[..2]), [[0; 26]; 5]);
+        }
+    }
+
+    mod describe_state {
+        use crate::{describe_state, DictionaryWithCounts, RepresentableAsWord, WORD_SIZE};
+
+        #[test]
+        fn lists_few_answers_by_count() {
+            let remaining =
+                DictionaryWithCounts::from([(b"hello", 3.0), (b"world", 5.0), (b"moved", 3.0)]);
+
+            assert_eq!(
+                describe_state(&remaining),
+                "3 possible answers left: world, hello, moved"
+            );
+            assert_eq!(
+                describe_state(&DictionaryWithCounts::from([(b"hello", 1.0)])),
+                "1 possible answer left: hello"
+            );
+            assert_eq!(
+                describe_state(&DictionaryWithCounts::<WORD_SIZE>::new()),
+                "0 possible answers left"
+            );
+        }
+
+        #[test]
+        fn only_counts_many_answers() {
+            let remaining = DictionaryWithCounts::from_iter(
+                include_str!("../dictionary.txt")
+                    .split_ascii_whitespace()
+                    .take(11)
+                    .map(|word_str| (word_str.as_word(), 1.0)),
+            );
+
+            assert_eq!(describe_state(&remaining), "11 possible answers left");
+        }
+    }
+
+    mod running_stats {
+        use crate::RunningStats;
+
+        #[test]
+        fn matches_the_batch_computation() {
+            let scores = [3, 4, 2, 5, 3, 3, 6, 4, 1, 3, 32];
+            let mut stats = RunningStats::new();
+            for score in scores {
+                stats.push(score);
+            }
+
+            let n = scores.len() as f64;
+            let mean = scores.iter().sum::<usize>() as f64 / n;
+            let variance = scores
+                .iter()
+                .map(|&score| (score as f64 - mean).powi(2))
+                .sum::<f64>()
+                / n;
+
+            assert_eq!(stats.count(), scores.len());
+            assert!((stats.mean().unwrap() - mean).abs() < 1e-12);
+            assert!((stats.variance().unwrap() - variance).abs() < 1e-12);
+            assert!((stats.stddev().unwrap() - variance.sqrt()).abs() < 1e-12);
+        }
+
+        #[test]
+        fn no_scores_have_no_statistics() {
+            let stats = RunningStats::new();
+
+            assert_eq!(stats.mean(), None);
+            assert_eq!(stats.variance(), None);
+            assert_eq!(stats.stddev(), None);
+
+            let mut stats = stats;
+            stats.push(4);
+            assert_eq!(stats.mean(), Some(4.0));
+            assert_eq!(stats.variance(), Some(0.0));
+        }
+    }
+
+    mod normalized_counts {
+        use crate::{DictionaryWithCounts, NormalizedCounts, Word};
+
+        const COUNTS: [(&Word, f64); 3] = [(b"hello", 3.0), (b"world", 1.0), (b"aalii", 0.0)];
+
+        #[test]
+        fn probabilities_sum_to_one() {
+            let normalized = NormalizedCounts::new(DictionaryWithCounts::from(COUNTS));
+
+            let sum = normalized.probabilities().map(|(_, p)| p).sum::<f64>();
+            assert!((sum - 1.0).abs() < 1e-12);
+            assert_eq!(normalized.probability(b"hello"), 0.75);
+            assert_eq!(normalized.probability(b"aalii"), 0.0);
+            assert_eq!(normalized.probability(b"crane"), 0.0);
+            assert_eq!(normalized.reciprocal(), 0.25);
+        }
+
+        #[test]
+        fn smoothing_gives_zero_counts_a_probability() {
+            let normalized =
+                NormalizedCounts::new(DictionaryWithCounts::from(COUNTS)).laplace_smoothing(1.0);
+
+            let sum = normalized.probabilities().map(|(_, p)| p).sum::<f64>();
+            assert!((sum - 1.0).abs() < 1e-12);
+            assert_eq!(normalized.probability(b"hello"), 4.0 / 7.0);
+            assert_eq!(normalized.probability(b"world"), 2.0 / 7.0);
+            assert_eq!(normalized.probability(b"aalii"), 1.0 / 7.0);
+        }
+
+        #[test]
+        fn zero_total_gives_zero_probabilities() {
+            let normalized = NormalizedCounts::new(DictionaryWithCounts::from([(b"aalii", 0.0)]));
+
+            assert_eq!(normalized.probability(b"aalii"), 0.0);
+        }
+    }
+
+    mod word_display {
+        use crate::{word_str, Correctness, GameRecord, Guess, PlayError, WordDisplay};
+
+        #[test]
+        fn word_str_is_the_text() {
+            assert_eq!(word_str(b"hello"), "hello");
+        }
+
+        #[test]
+        #[should_panic(expected = "A word has to be ASCII")]
+        fn word_str_rejects_words_that_are_not_ascii() {
+            word_str(&[b'h', b'e', b'l', b'l', 0xff]);
+        }
+
+        #[test]
+        fn words_display_and_debug_as_text() {
+            assert_eq!(WordDisplay(b"hello").to_string(), "hello");
+            assert_eq!(format!("{:>7}", WordDisplay(b"hello")), "  hello");
+            assert_eq!(format!("{:?}", WordDisplay(b"hello")), "\"hello\"");
+            assert_eq!(WordDisplay(&[b'h', b'i', 0xff]).to_string(), "hi\u{fffd}");
+        }
+
+        #[test]
+        fn errors_and_records_debug_their_words_as_text() {
+            let error = PlayError::IllegalGuess {
+                attempt: 2,
+                word: *b"xyzzy",
+            };
+            assert_eq!(
+                format!("{:?}", error),
+                "IllegalGuess { attempt: 2, word: \"xyzzy\" }"
+            );
+
+            let record = GameRecord {
+                answer: b"moved",
+                guesses: vec![Guess::new(b"moved", b"moved")],
+                outcome: Some(1),
+                expected_information: vec![None],
+            };
+            let debug = format!("{:?}", record);
+            assert!(debug
+                .starts_with("GameRecord { answer: \"moved\", guesses: [Guess { word: \"moved\""));
+            assert!(debug.contains(&format!("{:?}", [Correctness::Correct; 5])));
+        }
+    }
+
+    mod as_word {
+        use crate::{RepresentableAsWord, Word, WordError};
+
+        #[test]
+        fn word_macro_is_the_byte_literal() {
+            let word: &'static Word = word!("hello");
+
+            assert_eq!(word, b"hello");
+        }
+
+        #[test]
+        fn five_letters() {
+            assert_eq!("hello".try_as_word(), Ok(b"hello"));
+        }
+
+        #[test]
+        fn empty_is_too_short() {
+            assert_eq!("".try_as_word(), Err(WordError::TooShort { len: 0 }));
+        }
+
+        #[test]
+        fn three_letters_are_too_short() {
+            assert_eq!("abc".try_as_word(), Err(WordError::TooShort { len: 3 }));
+        }
+
+        #[test]
+        fn six_letters_are_too_long() {
+            assert_eq!("planet".try_as_word(), Err(WordError::TooLong { len: 6 }));
+            assert_eq!("planet".as_word(), b"plane");
+        }
+
+        #[test]
+        fn accents_are_not_ascii() {
+            assert_eq!("café".try_as_word(), Err(WordError::NotAscii));
+        }
+
+        #[test]
+        fn digits_and_punctuation_are_kept() {
+            assert_eq!("b4ker".try_as_word(), Ok(b"b4ker"));
+            assert_eq!("o'dea".try_as_word(), Ok(b"o'dea"));
+            assert_eq!("B4KER".as_word_normalized(), *b"b4ker");
+        }
+
+        #[test]
+        fn normalized_ignores_case() {
+            assert_eq!("WORLD".as_word_normalized(), *"world".as_word());
+            assert_eq!("WoRlD".as_word_normalized(), "world".as_word_normalized());
+        }
+
+        #[test]
+        fn byte_slices_are_borrowed() {
+            let bytes: &[u8] = b"crane\nslate";
+            let word = bytes[..5].as_word();
+
+            assert_eq!(word, b"crane");
+            // The word points into the original buffer rather than into a copy.
+            assert!(std::ptr::eq(word.as_ptr(), bytes.as_ptr()));
+            assert_eq!(bytes[6..].try_as_word(), Ok(b"slate"));
+        }
+
+        #[test]
+        fn arrays_are_their_own_word() {
+            let word = *b"crane";
+
+            assert!(std::ptr::eq(word.as_word(), &word));
+            assert_eq!(
+                b"caf\xc3\xa9".as_slice().try_as_word(),
+                Err(WordError::NotAscii)
+            );
+        }
+
+        #[test]
+        #[should_panic]
+        fn as_word_panics_on_short_strings() {
+            "abc".as_word();
+        }
+    }
+
+    mod with_opener {
+        use crate::algorithms::Unoptimized;
+        use crate::{PlayError, RepresentableAsWord, WithOpener, Wordle};
+
+        const DICTIONARY: &str = include_str!("../dictionary.txt");
+
+        #[test]
+        fn opener_is_guessed_first() {
+            let wordle: Wordle = Wordle::new(
+                DICTIONARY
+                    .split_ascii_whitespace()
+                    .step_by(20)
+                    .chain(["tares", "moved"])
+                    .map(|word_str| word_str.as_word()),
+            );
+            let guesser = WithOpener::new(
+                b"tares",
+                wordle.get_dictionary(),
+                Unoptimized::new(
+                    wordle.get_dictionary(),
+                    wordle.get_possible_answers().clone(),
+                ),
+            )
+            .unwrap();
+
+            let record = wordle.play_recorded(b"moved", guesser);
+
+            assert_eq!(record.guesses[0].word, b"tares");
+            assert!(record.outcome.is_some());
+            assert_eq!(record.guesses.last().unwrap().word, b"moved");
+        }
+
+        #[test]
+        fn opener_has_to_be_in_the_dictionary() {
+            let wordle: Wordle = Wordle::new([b"hello", b"world"]);
+
+            assert_eq!(
+                WithOpener::new(
+                    b"tares",
+                    wordle.get_dictionary(),
+                    Unoptimized::new(
+                        wordle.get_dictionary(),
+                        wordle.get_possible_answers().clone()
+                    ),
+                )
+                .err(),
+                Some(PlayError::IllegalGuess {
+                    attempt: 1,
+                    word: *b"tares"
+                })
+            );
+        }
+    }
+
+    mod game_context {
+        use crate::{GameContext, Guess, Guesser, Word, Wordle};
+        use std::cell::RefCell;
+
+        /// Guesses `words` in order, and notes down every context it's given.
+        struct Recorder<'a> {
+            words: &'a [&'static Word],
+            contexts: &'a RefCell<Vec<GameContext>>,
+        }
+
+        impl<'a> Guesser<'static> for Recorder<'a> {
+            fn guess(&mut self, past_guesses: &[Guess]) -> &'static Word {
+                self.words[past_guesses.len()]
+            }
+
+            fn guess_with_context(
+                &mut self,
+                past_guesses: &[Guess<'static>],
+                context: &GameContext,
+            ) -> &'static Word {
+                self.contexts.borrow_mut().push(*context);
+                self.guess(past_guesses)
+            }
+        }
+
+        #[test]
+        fn play_counts_the_remaining_answers() {
+            let wordle = Wordle::new([b"hello", b"jello", b"world", b"moved"]);
+            let contexts = RefCell::new(Vec::new());
+            let guesser = Recorder {
+                words: &[b"world", b"hello", b"jello"],
+                contexts: &contexts,
+            };
+
+            // "world" leaves "hello" and "jello", and "hello" then leaves only "jello".
+            assert_eq!(wordle.play(b"jello", guesser), Some(3));
+            assert_eq!(
+                contexts.into_inner(),
+                [
+                    GameContext {
+                        attempt: 1,
+                        remaining_hint: Some(4)
+                    },
+                    GameContext {
+                        attempt: 2,
+                        remaining_hint: Some(2)
+                    },
+                    GameContext {
+                        attempt: 3,
+                        remaining_hint: Some(1)
+                    },
+                ]
+            );
+        }
+    }
+
+    mod play_variants {
+        use crate::{Correctness, Guess, Guesser, RepresentableAsWord, Word, Wordle};
+
+        const DICTIONARY: &str = "planet planer banana orange purple yellow";
+
+        /// Guesses the first word of its list that every past guess allows.
+        struct FirstAllowed(Vec<&'static Word<6>>);
+
+        impl Guesser<'static, 6> for FirstAllowed {
+            fn guess(&mut self, past_guesses: &[Guess<6>]) -> &'static Word<6> {
+                self.0
+                    .iter()
+                    .find(|word| past_guesses.iter().all(|guess| guess.allows(word)))
+                    .expect("The answer is always allowed")
+            }
+        }
+
+        #[test]
+        fn check_six_letter_words() {
+            assert_eq!(Correctness::check(b"planet", b"planer"), mask![C C C C C W]);
+            assert_eq!(Correctness::check(b"banana", b"orange"), mask![W W M M W W]);
+        }
+
+        #[test]
+        fn play_six_letter_dictionary() {
+            let words = Vec::from_iter(
+                DICTIONARY
+                    .split_ascii_whitespace()
+                    .map(|word_str| word_str.as_sized_word::<6>()),
+            );
+            let wordle: Wordle<6> = Wordle::new(words.iter().copied());
+
+            for &answer in &words {
+                let record = wordle.play_recorded(answer, FirstAllowed(words.clone()));
+                assert_eq!(record.guesses.last().map(|guess| guess.word), Some(answer));
+            }
+        }
+    }
+
+    mod check_correctness {
+        use crate::Correctness;
+
+        #[test]
+        fn all_green() {
+            assert_eq!(Correctness::check(b"hello", b"hello"), mask![C C C C C]);
+        }
 
-pub type Word = [u8; WORD_SIZE];
+        #[test]
+        fn all_gray() {
+            assert_eq!(Correctness::check(b"hello", b"pqrst"), mask![W W W W W]);
+        }
 
-/// A Dictionary is a set of words.
-pub type Dictionary = HashSet<&'static Word>;
+        #[test]
+        fn all_yellow() {
+            assert_eq!(Correctness::check(b"hello", b"llohe"), mask![M M M M M]);
+        }
 
-/// A DictionaryWithCounts is a set of words alongside a usize that gives us an indication of how
-/// frequently this word is encountered in the English language.
-pub type DictionaryWithCounts = HashMap<&'static Word, f64>;
+        #[test]
+        fn actual_words() {
+            assert_eq!(Correctness::check(b"hello", b"world"), mask![W M W C W]);
+        }
 
-pub trait RepresentableAsWord {
-    fn as_word(&self) -> &Word;
-}
+        #[test]
+        fn digits_are_letters_like_any_other() {
+            assert_eq!(Correctness::check(b"abc12", b"21abc"), mask![M M M M M]);
+            assert_eq!(Correctness::check(b"r2d22", b"22222"), mask![W C W C C]);
+        }
 
-impl RepresentableAsWord for str {
-    fn as_word(&self) -> &Word {
-        let (chunks, _): (&[[u8; WORD_SIZE]], &[u8]) = self.as_bytes().as_chunks();
+        #[test]
+        fn guess_single_letter() {
+            assert_eq!(Correctness::check(b"hello", b"lllll"), mask![W W C C W]);
+        }
 
-        return &chunks[0];
-    }
-}
+        #[test]
+        fn guess_with_more_of_a_letter_than_needed() {
+            assert_eq!(Correctness::check(b"azzaz", b"aaabb"), mask![C M W W W]);
+        }
 
-pub struct Wordle {
-    dictionary: Dictionary,
-}
+        #[test]
+        fn check_into_overwrites_reused_buffer() {
+            let mut out = mask![C C C C C];
+            Correctness::check_into(b"hello", b"world", &mut out);
+            assert_eq!(out, mask![W M W C W]);
 
-impl Wordle {
-    pub fn new<I: IntoIterator<Item = &'static Word>>(iter: I) -> Self {
-        Self {
-            dictionary: Dictionary::from_iter(iter),
+            Correctness::check_into(b"hello", b"pqrst", &mut out);
+            assert_eq!(out, mask![W W W W W]);
         }
-    }
 
-    pub fn get_dictionary(&self) -> &Dictionary {
-        return &self.dictionary;
+        #[test]
+        fn check_at_compile_time() {
+            const MASK: [Correctness; 5] = Correctness::check(b"hello", b"lllll");
+            assert_eq!(MASK, mask![W W C C W]);
+        }
     }
 
-    /// A function play that takes a generic G that implements the trait Guesser.
-    pub fn play<G: Guesser>(&self, answer: &'static Word, mut guesser: G) -> Option<usize> {
-        // play six rounds where it invokes the guesser each round
-        let mut past_guesses = Vec::new();
+    mod duplicate_letters {
+        use crate::{Correctness, Guess, PackedCorrectness, Word};
 
-        for attempt_index in 1..=TRIES_BEFORE_LOSS {
-            let guessed_word = guesser.guess(&past_guesses[..]);
-            assert!(self.dictionary.contains(&guessed_word));
+        /// Answers and guesses with repeated letters, and the masks Wordle shows for them. Greens
+        /// are handed out first, and then yellows go to the leftmost remaining copies of a letter
+        /// in the guess, one per copy of the letter in the answer that isn't green.
+        const CASES: [(&Word, &Word, [Correctness; 5]); 14] = [
+            (b"eerie", b"teree", mask![W C C M C]),
+            (b"abbey", b"bobby", mask![M W C W C]),
+            (b"geese", b"eerie", mask![M C W W C]),
+            (b"speed", b"geese", mask![W M C M W]),
+            (b"lever", b"eerie", mask![M C M W W]),
+            (b"mamma", b"amass", mask![M M M W W]),
+            (b"sassy", b"asses", mask![M M C W M]),
+            (b"hello", b"lllll", mask![W W C C W]),
+            (b"robot", b"ooooo", mask![W C W C W]),
+            (b"abide", b"speed", mask![W W M W M]),
+            (b"crane", b"nanny", mask![W M W C W]),
+            (b"those", b"geese", mask![W W W C C]),
+            (b"eerie", b"eerie", mask![C C C C C]),
+            (b"steel", b"leets", mask![M M C M M]),
+        ];
 
-            if guessed_word.eq(answer) {
-                return Some(attempt_index);
+        #[test]
+        fn check_matches_every_case() {
+            for (answer, guess, mask) in CASES {
+                assert_eq!(
+                    Correctness::check(answer, guess),
+                    mask,
+                    "{} against {}",
+                    String::from_utf8_lossy(guess),
+                    String::from_utf8_lossy(answer)
+                );
             }
+        }
 
-            let correctness_mask = Correctness::check(answer, guessed_word);
-            past_guesses.push(Guess {
-                word: guessed_word,
-                mask: correctness_mask,
-            });
+        #[test]
+        fn check_packed_matches_every_case() {
+            for (answer, guess, mask) in CASES {
+                assert_eq!(
+                    Correctness::check_packed(answer, guess),
+                    PackedCorrectness::from(mask)
+                );
+            }
         }
 
-        None
+        #[test]
+        fn every_case_allows_its_answer() {
+            for (answer, guess, mask) in CASES {
+                assert!(Guess::from_mask(guess, mask).allows(answer));
+                assert!(Correctness::is_consistent(guess, &mask));
+            }
+        }
     }
-}
 
-#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
-pub enum Correctness {
-    /// Green
-    Correct,
-    /// Yellow
-    Misplaced,
-    /// Gray
-    Wrong,
-}
+    mod consistency {
+        use crate::{Correctness, RepresentableAsWord};
 
-impl Correctness {
-    pub fn check(answer: &'static Word, guessed_word: &Word) -> [Self; WORD_SIZE] {
-        let mut rv = [Self::Wrong; WORD_SIZE];
-        let mut used = [false; WORD_SIZE];
-        for i in 0..WORD_SIZE {
-            if answer[i] == guessed_word[i] {
-                rv[i] = Self::Correct;
-                used[i] = true;
-            };
+        const DICTIONARY: &str = include_str!("../dictionary.txt");
+
+        #[test]
+        fn geese_with_a_green_last_e() {
+            assert!(Correctness::is_consistent(b"geese", &mask![W W W C C]));
+            assert!(Correctness::is_consistent(b"geese", &mask![W M W C C]));
         }
 
-        // Check can't just check for misplaced using: `answer.contains(&guessed_word[i])`
-        // because it takes care of counts when deciding whether it is misplaced or wrong.
-        for i in 0..WORD_SIZE {
-            for j in 0..WORD_SIZE {
-                if rv[i] != Self::Correct && !used[j] && answer[j] == guessed_word[i] {
-                    rv[i] = Self::Misplaced;
-                    used[j] = true;
-                    break;
+        #[test]
+        fn geese_with_a_gray_e_before_a_yellow_e() {
+            assert!(!Correctness::is_consistent(b"geese", &mask![W W M C C]));
+            assert!(!Correctness::is_consistent(b"geese", &mask![W W M W M]));
+        }
+
+        #[test]
+        fn geese_with_mixed_green_yellow_and_gray_es() {
+            assert!(Correctness::is_consistent(b"geese", &mask![W C M W W]));
+            assert!(Correctness::is_consistent(b"geese", &mask![W M C W W]));
+            assert!(!Correctness::is_consistent(b"geese", &mask![W C W W M]));
+        }
+
+        #[test]
+        fn yellows_with_nowhere_to_go() {
+            assert!(!Correctness::is_consistent(b"geese", &mask![W M M C C]));
+            assert!(!Correctness::is_consistent(b"aaaab", &mask![M M M M W]));
+            assert!(!Correctness::is_consistent(b"abcde", &mask![C C C C M]));
+        }
+
+        #[test]
+        fn every_checked_mask_is_consistent() {
+            let words: Vec<_> = DICTIONARY
+                .split_ascii_whitespace()
+                .step_by(97)
+                .map(|word_str| word_str.as_word())
+                .collect();
+
+            for &answer in &words {
+                for guess in &words {
+                    assert!(Correctness::is_consistent(
+                        guess,
+                        &Correctness::check(answer, guess)
+                    ));
                 }
             }
         }
-
-        rv
     }
-}
 
-pub struct Guess {
-    pub word: &'static Word,
-    pub mask: [Correctness; WORD_SIZE],
-}
+    mod guess {
+        use crate::{Correctness, Guess};
+        use std::collections::HashSet;
 
-pub trait Guesser {
-    fn guess(&mut self, past_guesses: &[Guess]) -> &'static Word;
-}
+        #[test]
+        fn new_and_from_mask_agree() {
+            let guess_with_a_computed_mask = Guess::new(b"rebut", b"crane");
+            let guess_with_a_given_mask = Guess::from_mask(b"crane", mask![W M W W M]);
 
-/// We want to allow functions to be guessers, which just calls `self` on `past_guesses`.
-impl Guesser for fn(past_guesses: &[Guess]) -> &'static Word {
-    fn guess(&mut self, past_guesses: &[Guess]) -> &'static Word {
-        (*self)(past_guesses)
-    }
-}
+            assert_eq!(
+                guess_with_a_computed_mask.word,
+                guess_with_a_given_mask.word
+            );
+            assert_eq!(
+                guess_with_a_computed_mask.mask,
+                guess_with_a_given_mask.mask
+            );
+            assert_eq!(
+                Guess::new(b"rebut", b"rebut").mask,
+                [Correctness::Correct; 5]
+            );
+        }
 
-#[cfg(test)]
-mod tests {
-    mod play_wordle {
-        use crate::{Guess, Guesser, RepresentableAsWord, Word, Wordle};
+        #[test]
+        fn equal_guesses_are_deduplicated() {
+            let histories = HashSet::from([
+                vec![
+                    Guess::new(b"rebut", b"crane"),
+                    Guess::new(b"rebut", b"sloth"),
+                ],
+                vec![
+                    Guess::from_mask(b"crane", mask![W M W W M]),
+                    Guess::from_mask(b"sloth", mask![W W W M W]),
+                ],
+                vec![Guess::new(b"rebut", b"crane")],
+            ]);
+            assert_eq!(histories.len(), 2);
 
-        macro_rules! guesser {
-            ($func:expr) => {
-                ($func) as fn(past_guesses: &[Guess]) -> &'static Word
-            };
+            let guesses = HashSet::from([
+                Guess::new(b"rebut", b"crane"),
+                Guess::new(b"rebut", b"crane"),
+                Guess::new(b"crane", b"crane"),
+            ]);
+            assert_eq!(guesses.len(), 2);
+            assert_eq!(
+                format!("{:?}", Guess::new(b"rebut", b"crane")),
+                "Guess { word: \"crane\", mask: [Wrong, Misplaced, Wrong, Wrong, Misplaced] }"
+            );
         }
+    }
 
-        const DICTIONARY: &'static str = include_str!("../dictionary.txt");
+    mod filter_candidates {
+        use crate::{Correctness, Guess, RepresentableAsWord, Word};
+
+        const DICTIONARY: &str = include_str!("../dictionary.txt");
 
         #[test]
-        fn guess_first_time_correctly() {
-            let wordle: Wordle = Wordle::new(
+        fn one_word_left_after_four_clues() {
+            let dictionary = Vec::from_iter(
                 DICTIONARY
                     .split_ascii_whitespace()
                     .map(|word_str| word_str.as_word()),
             );
+            let guesses: [&'static Word; 4] = [b"crane", b"sloth", b"dumpy", b"bewig"];
+            let past = guesses.map(|word| Guess {
+                word,
+                mask: Correctness::check(b"rebut", word),
+            });
+
+            assert_eq!(Guess::filter_candidates(&past[..3], &dictionary).len(), 3);
+            assert_eq!(Guess::filter_candidates(&past, &dictionary), [b"rebut"]);
+        }
+
+        #[test]
+        fn words_with_digits_filter_byte_by_byte() {
+            let words: [&'static Word; 4] = [b"r2d2s", b"c3p0s", b"r2222", b"hello"];
+            let past = [Guess {
+                word: b"r2d2s",
+                mask: Correctness::check(b"r2222", b"r2d2s"),
+            }];
+
+            assert_eq!(Guess::filter_candidates(&past, &words), [b"r2222"]);
+        }
+
+        #[test]
+        fn no_clues_keep_everything() {
+            let words: [&'static Word; 2] = [b"hello", b"world"];
+
+            assert_eq!(Guess::filter_candidates(&[], &words), words);
+        }
+    }
+
+    mod letters {
+        use crate::{Correctness, Guess, ParseMaskError};
+
+        #[test]
+        fn letters_round_trip() {
+            for correctness in [
+                Correctness::Correct,
+                Correctness::Misplaced,
+                Correctness::Wrong,
+            ] {
+                assert_eq!(
+                    Correctness::from_letter(correctness.as_letter()),
+                    Ok(correctness)
+                );
+            }
+            assert_eq!(Correctness::from_letter('m'), Ok(Correctness::Misplaced));
+            assert_eq!(
+                Correctness::from_letter('Q'),
+                Err(ParseMaskError::UnknownSymbol('Q'))
+            );
+        }
 
+        #[test]
+        fn masks_parse_from_letters_colors_or_emoji() {
+            assert_eq!(Correctness::parse_mask("WMCWW"), Ok(mask![W M C W W]));
+            assert_eq!(Correctness::parse_mask("bygbb"), Ok(mask![W M C W W]));
             assert_eq!(
-                wordle.play(b"moved", guesser!(|_past: &[Guess]| b"moved")),
-                Some(1)
+                Correctness::parse_mask(" ⬛🟨🟩⬛⬛\n"),
+                Ok(mask![W M C W W])
+            );
+            assert_eq!(
+                Correctness::parse_mask("bygb"),
+                Err(ParseMaskError::WrongLength(4))
             );
         }
 
         #[test]
-        fn guess_third_time_correctly() {
-            let wordle: Wordle = Wordle::new(
-                DICTIONARY
-                    .split_ascii_whitespace()
-                    .map(|word_str| word_str.as_word()),
+        fn guess_displays_word_and_mask() {
+            let guess = Guess {
+                word: b"crane",
+                mask: mask![W M C W W],
+            };
+
+            assert_eq!(guess.to_string(), "crane WMCWW");
+        }
+    }
+
+    mod emoji {
+        use crate::{Correctness, ParseMaskError, RenderStyle};
+
+        #[test]
+        fn renders_dark_and_light_mode() {
+            assert_eq!(Correctness::to_emoji(&mask![C M W W C], true), "🟩🟨⬛⬛🟩");
+            assert_eq!(
+                Correctness::to_emoji(&mask![C M W W C], false),
+                "🟩🟨⬜⬜🟩"
             );
+        }
+
+        #[test]
+        fn every_style_has_its_own_glyphs() {
+            let mask = mask![C M W W C];
 
             assert_eq!(
-                wordle.play(
-                    b"moved",
-                    guesser!(|past: &[Guess]| if past.len() == 2 { b"moved" } else { b"which" })
-                ),
-                Some(3)
+                Correctness::render(&mask, RenderStyle::Emoji, true),
+                "🟩🟨⬛⬛🟩"
+            );
+            assert_eq!(
+                Correctness::render(&mask, RenderStyle::ColorblindEmoji, true),
+                "🟧🟦⬛⬛🟧"
             );
+            assert_eq!(
+                Correctness::render(&mask, RenderStyle::ColorblindEmoji, false),
+                "🟧🟦⬜⬜🟧"
+            );
+            assert_eq!(
+                Correctness::render(&mask, RenderStyle::Ascii, true),
+                "CMWWC"
+            );
+            assert_eq!(Correctness::from_emoji("🟧🟦⬛⬛🟧"), Ok(mask));
         }
 
         #[test]
-        fn dont_guess_correctly() {
-            let wordle: Wordle = Wordle::new(
-                DICTIONARY
-                    .split_ascii_whitespace()
-                    .map(|word_str| word_str.as_word()),
+        fn hand_written_lines_round_trip() {
+            for line in ["⬛🟨⬛🟩⬛", "🟩🟩🟩🟩🟩", "⬜⬜⬜⬜⬜", "🟨🟨⬜🟩🟨"]
+            {
+                let dark_mode = !line.contains('⬜');
+                let mask = Correctness::from_emoji(line).unwrap();
+                assert_eq!(Correctness::to_emoji(&mask, dark_mode), line);
+            }
+        }
+
+        #[test]
+        fn every_mask_round_trips() {
+            for mask in Correctness::all_masks() {
+                for dark_mode in [true, false] {
+                    let emoji = Correctness::to_emoji(&mask, dark_mode);
+                    assert_eq!(Correctness::from_emoji(&emoji), Ok(mask));
+                }
+            }
+        }
+
+        #[test]
+        fn parses_dark_mode() {
+            assert_eq!(Correctness::from_emoji("⬛🟨⬛🟩⬛"), Ok(mask![W M W C W]));
+        }
+
+        #[test]
+        fn parses_light_mode() {
+            assert_eq!(Correctness::from_emoji("🟩⬜⬜🟨🟩"), Ok(mask![C W W M C]));
+        }
+
+        #[test]
+        fn ignores_variation_selectors() {
+            assert_eq!(
+                Correctness::from_emoji("⬛\u{FE0F}⬛\u{FE0F}🟩🟩🟩"),
+                Ok(mask![W W C C C])
             );
+        }
 
-            pub struct DoesNotGuessCorrectly;
+        #[test]
+        fn rejects_wrong_length() {
+            assert_eq!(
+                Correctness::from_emoji("🟩🟩🟩🟩"),
+                Err(ParseMaskError::WrongLength(4))
+            );
+            assert_eq!(
+                Correctness::from_emoji("🟩🟩🟩🟩🟩🟩"),
+                Err(ParseMaskError::WrongLength(6))
+            );
+        }
 
-            impl Guesser for DoesNotGuessCorrectly {
-                fn guess(&mut self, _past_guesses: &[Guess]) -> &'static Word {
-                    b"which"
+        #[test]
+        fn rejects_unknown_symbols() {
+            assert_eq!(
+                Correctness::from_emoji("🟩🟥🟩🟩🟩"),
+                Err(ParseMaskError::UnknownSymbol('🟥'))
+            );
+        }
+    }
+
+    mod correctness_cache {
+        use crate::{Correctness, CorrectnessCache, Word};
+
+        const WORDS: [&Word; 4] = [b"hello", b"world", b"azzaz", b"aaabb"];
+
+        #[test]
+        fn matches_check_for_every_pair() {
+            let cache = CorrectnessCache::new(&WORDS);
+            assert_eq!(cache.len(), WORDS.len() * WORDS.len());
+
+            for (answer_idx, answer) in WORDS.iter().enumerate() {
+                for (guess_idx, guess) in WORDS.iter().enumerate() {
+                    assert_eq!(
+                        cache.get(answer_idx, guess_idx),
+                        Correctness::check_packed(answer, guess)
+                    );
                 }
             }
+        }
 
-            assert_eq!(wordle.play(b"moved", DoesNotGuessCorrectly {}), None);
+        #[test]
+        fn answer_subset_only_caches_those_answers() {
+            let cache = CorrectnessCache::builder(&WORDS)
+                .answers(&WORDS[..1])
+                .build();
+            assert_eq!(cache.len(), WORDS.len());
+
+            assert_eq!(
+                cache.get_by_word(b"hello", b"world"),
+                Some(Correctness::check_packed(b"hello", b"world"))
+            );
+            assert_eq!(cache.get_by_word(b"world", b"hello"), None);
         }
     }
 
-    mod check_correctness {
-        use crate::Correctness;
+    mod packed_correctness {
+        use crate::{Correctness, PackedCorrectness};
+        use std::collections::HashSet;
 
-        macro_rules! mask {
-            (C) => {Correctness::Correct};
-            (M) => {Correctness::Misplaced};
-            (W) => {Correctness::Wrong};
-            ($($c:tt)+) => {[$(mask!($c)),+]}
+        #[test]
+        fn all_green_packs_to_zero() {
+            assert_eq!(PackedCorrectness::from(mask![C C C C C]).as_u8(), 0);
         }
 
         #[test]
-        fn all_green() {
-            assert_eq!(Correctness::check(b"hello", b"hello"), mask![C C C C C]);
+        fn all_gray_packs_to_last() {
+            assert_eq!(
+                PackedCorrectness::from(mask![W W W W W]).as_index(),
+                PackedCorrectness::COUNT - 1
+            );
         }
 
         #[test]
-        fn all_gray() {
-            assert_eq!(Correctness::check(b"hello", b"pqrst"), mask![W W W W W]);
+        fn round_trips_every_byte() {
+            for value in 0..PackedCorrectness::COUNT as u8 {
+                let packed = PackedCorrectness::try_from(value).unwrap();
+                assert_eq!(PackedCorrectness::from(packed.unpack()), packed);
+            }
         }
 
         #[test]
-        fn all_yellow() {
-            assert_eq!(Correctness::check(b"hello", b"llohe"), mask![M M M M M]);
+        fn all_masks_are_distinct() {
+            let masks = HashSet::<[Correctness; 5]>::from_iter(Correctness::all_masks());
+
+            assert_eq!(Correctness::all_masks().count(), PackedCorrectness::COUNT);
+            assert_eq!(masks.len(), 243);
+            assert!(masks.contains(&[Correctness::Correct; 5]));
         }
 
         #[test]
-        fn actual_words() {
-            assert_eq!(Correctness::check(b"hello", b"world"), mask![W M W C W]);
+        fn indices_round_trip_every_mask() {
+            for (index, mask) in Correctness::all_masks().enumerate() {
+                assert_eq!(Correctness::to_index(&mask), index);
+                assert_eq!(Correctness::from_index(Correctness::to_index(&mask)), mask);
+            }
+
+            assert_eq!(Correctness::to_index(&mask![C C C C C]), 0);
+            assert_eq!(Correctness::to_index(&mask![W W W W W]), 242);
+            assert_eq!(Correctness::to_index(&mask![C C C C M]), 1);
         }
 
         #[test]
-        fn guess_single_letter() {
-            assert_eq!(Correctness::check(b"hello", b"lllll"), mask![W W C C W]);
+        #[should_panic(expected = "There is no mask with index 243")]
+        fn indices_past_the_last_mask_panic() {
+            Correctness::from_index(243);
         }
 
         #[test]
-        fn guess_with_more_of_a_letter_than_needed() {
-            assert_eq!(Correctness::check(b"azzaz", b"aaabb"), mask![C M W W W]);
+        fn rejects_out_of_range_bytes() {
+            assert_eq!(PackedCorrectness::try_from(243), Err(243));
+        }
+
+        #[test]
+        fn check_packed_agrees_with_check() {
+            let pairs: [(&[u8; 5], &[u8; 5]); 5] = [
+                (b"hello", b"world"),
+                (b"hello", b"lllll"),
+                (b"azzaz", b"aaabb"),
+                (b"hello", b"llohe"),
+                (b"moved", b"which"),
+            ];
+            for (answer, guess) in pairs {
+                assert_eq!(
+                    Correctness::check_packed(answer, guess),
+                    PackedCorrectness::from(Correctness::check(answer, guess))
+                );
+            }
+        }
+
+        #[test]
+        fn check_packed_matches_check_on_random_pairs() {
+            // A xorshift PRNG over a small alphabet, so that duplicate letters come up often.
+            let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+            let mut next_word = || {
+                let mut word = [0; 5];
+                for letter in word.iter_mut() {
+                    state ^= state << 13;
+                    state ^= state >> 7;
+                    state ^= state << 17;
+                    *letter = b'a' + (state % 4) as u8;
+                }
+                word
+            };
+
+            for _ in 0..10_000 {
+                let answer = next_word();
+                let guess = next_word();
+                assert_eq!(
+                    crate::green_bits(&answer, &guess),
+                    crate::green_bits_scalar(&answer, &guess)
+                );
+                let mut mask = [Correctness::Wrong; 5];
+                Correctness::check_into(&answer, &guess, &mut mask);
+                assert_eq!(
+                    Correctness::check_packed(&answer, &guess),
+                    PackedCorrectness::from(mask)
+                );
+            }
+        }
+
+        #[test]
+        fn displays_as_letters() {
+            assert_eq!(
+                PackedCorrectness::from(mask![W M W C W]).to_string(),
+                "WMWCW"
+            );
         }
     }
 }
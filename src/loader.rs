@@ -0,0 +1,243 @@
+use crate::{Dictionary, DictionaryWithCounts, RepresentableAsWord, Word, WordDisplay, WordError};
+use std::path::Path;
+use std::{fmt, fs, io};
+
+/// Parses the word lists that ship with the crate, or any in the same format, into dictionaries.
+///
+/// Blank lines and lines starting with `#` are skipped. Any other line that can't be turned into a
+/// word is skipped too, and reported as a `LoadWarning` so that a bad line doesn't stop the load.
+pub struct DictionaryLoader;
+
+impl DictionaryLoader {
+    /// Parses a list of one word per line, like `dictionary.txt` or `answers.txt`.
+    pub fn from_wordlist(wordlist: &'static str) -> (Dictionary<'static>, Vec<LoadWarning>) {
+        let mut dictionary = Dictionary::new();
+        let mut warnings = Vec::new();
+
+        for (line, text) in Self::entries(wordlist) {
+            match text.try_as_word() {
+                Ok(word) if !dictionary.insert(word) => {
+                    warnings.push(LoadWarning::Duplicate { line, word })
+                }
+                Ok(_) => {}
+                Err(error) => warnings.push(LoadWarning::InvalidWord { line, error }),
+            }
+        }
+
+        (dictionary, warnings)
+    }
+
+    /// Parses a list of one word and its count per line, separated by a space, like `joined.txt`.
+    pub fn from_counts(
+        wordlist: &'static str,
+    ) -> (DictionaryWithCounts<'static>, Vec<LoadWarning>) {
+        let mut dictionary = DictionaryWithCounts::new();
+        let mut warnings = Vec::new();
+
+        for (line, text) in Self::entries(wordlist) {
+            let Some((word, count)) = text.split_once(' ') else {
+                warnings.push(LoadWarning::MissingCount { line });
+                continue;
+            };
+
+            let word = match word.try_as_word() {
+                Ok(word) => word,
+                Err(error) => {
+                    warnings.push(LoadWarning::InvalidWord { line, error });
+                    continue;
+                }
+            };
+
+            let Ok(count) = count.trim().parse() else {
+                warnings.push(LoadWarning::InvalidCount { line });
+                continue;
+            };
+
+            if dictionary.contains_key(word) {
+                warnings.push(LoadWarning::Duplicate { line, word });
+            } else {
+                dictionary.insert(word, count);
+            }
+        }
+
+        (dictionary, warnings)
+    }
+
+    /// The lines worth parsing, alongside their 1-based line numbers.
+    fn entries(wordlist: &'static str) -> impl Iterator<Item = (usize, &'static str)> {
+        wordlist
+            .lines()
+            .enumerate()
+            .map(|(i, text)| (i + 1, text.trim()))
+            .filter(|(_, text)| !text.is_empty() && !text.starts_with('#'))
+    }
+}
+
+/// A word list read from disk at runtime, for lists that aren't compiled in.
+///
+/// Words are `&'static`, so the contents of the file are leaked with `Box::leak` to live for the
+/// rest of the program. That memory is never given back, so a list should be read once and its
+/// dictionaries shared, rather than read again for every game.
+#[derive(Debug, Copy, Clone)]
+pub struct OwnedDictionary {
+    contents: &'static str,
+}
+
+impl OwnedDictionary {
+    /// Reads the file at `path`, which fails if it can't be read or isn't UTF-8.
+    pub fn from_file(path: impl AsRef<Path>) -> io::Result<Self> {
+        let contents = fs::read_to_string(path)?;
+
+        Ok(Self {
+            contents: Box::leak(contents.into_boxed_str()),
+        })
+    }
+
+    /// Parses the file as a list of one word per line, see `DictionaryLoader::from_wordlist`.
+    pub fn wordlist(&self) -> (Dictionary<'static>, Vec<LoadWarning>) {
+        DictionaryLoader::from_wordlist(self.contents)
+    }
+
+    /// Parses the file as a list of words and their counts, see `DictionaryLoader::from_counts`.
+    pub fn counts(&self) -> (DictionaryWithCounts<'static>, Vec<LoadWarning>) {
+        DictionaryLoader::from_counts(self.contents)
+    }
+}
+
+/// A line that `DictionaryLoader` skipped, and why.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum LoadWarning {
+    /// The line isn't a valid word.
+    InvalidWord { line: usize, error: WordError },
+    /// The word already appeared on an earlier line. The earlier line is the one that's kept.
+    Duplicate { line: usize, word: &'static Word },
+    /// The line has a word but no count after it.
+    MissingCount { line: usize },
+    /// The count after the word isn't a number.
+    InvalidCount { line: usize },
+}
+
+impl fmt::Display for LoadWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidWord { line, error } => write!(f, "line {}: {}", line, error),
+            Self::Duplicate { line, word } => write!(
+                f,
+                "line {}: {} appears more than once",
+                line,
+                WordDisplay(word)
+            ),
+            Self::MissingCount { line } => {
+                write!(f, "line {}: expected a word and a count", line)
+            }
+            Self::InvalidCount { line } => write!(f, "line {}: the count is not a number", line),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::algorithms::Unoptimized;
+    use crate::loader::{DictionaryLoader, LoadWarning, OwnedDictionary};
+    use crate::{WordError, Wordle};
+    use std::fs;
+
+    #[test]
+    fn wordlist_skips_blank_and_comment_lines() {
+        let (dictionary, warnings) =
+            DictionaryLoader::from_wordlist("# words\nhello\n\n  world \n");
+
+        assert_eq!(dictionary.len(), 2);
+        assert!(dictionary.contains(b"hello") && dictionary.contains(b"world"));
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn wordlist_warns_on_bad_and_duplicate_words() {
+        let (dictionary, warnings) = DictionaryLoader::from_wordlist("hello\nhi\ncafé\nhello\n");
+
+        assert_eq!(dictionary.len(), 1);
+        assert_eq!(
+            warnings,
+            [
+                LoadWarning::InvalidWord {
+                    line: 2,
+                    error: WordError::TooShort { len: 2 }
+                },
+                LoadWarning::InvalidWord {
+                    line: 3,
+                    error: WordError::NotAscii
+                },
+                LoadWarning::Duplicate {
+                    line: 4,
+                    word: b"hello"
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn counts_warn_on_lines_without_a_valid_count() {
+        let (dictionary, warnings) =
+            DictionaryLoader::from_counts("hello 10\nworld\nwhich lots\nmoved 2.5\n");
+
+        assert_eq!(dictionary.len(), 2);
+        assert_eq!(dictionary[b"hello"], 10.0);
+        assert_eq!(dictionary[b"moved"], 2.5);
+        assert_eq!(
+            warnings,
+            [
+                LoadWarning::MissingCount { line: 2 },
+                LoadWarning::InvalidCount { line: 3 },
+            ]
+        );
+    }
+
+    #[test]
+    fn shipped_lists_load_cleanly() {
+        let (dictionary, warnings) =
+            DictionaryLoader::from_wordlist(include_str!("../dictionary.txt"));
+        assert!(warnings.is_empty());
+
+        let (counts, warnings) = DictionaryLoader::from_counts(include_str!("../joined.txt"));
+        assert!(warnings.is_empty());
+        assert_eq!(counts.len(), dictionary.len());
+    }
+
+    #[test]
+    fn plays_a_game_from_a_file() {
+        let path = std::env::temp_dir().join(format!("roget-words-{}.txt", std::process::id()));
+        fs::write(&path, "hello\njello\nmello\nchjmx\n").unwrap();
+        let loaded = Wordle::from_file(&path);
+        fs::remove_file(&path).unwrap();
+
+        let (wordle, warnings) = loaded.unwrap();
+        assert!(warnings.is_empty());
+        assert_eq!(wordle.get_dictionary().len(), 4);
+
+        // "chjmx" tells the other three words apart.
+        let guesser = Unoptimized::new(
+            wordle.get_dictionary(),
+            wordle.get_possible_answers().clone(),
+        );
+        assert_eq!(wordle.play(b"mello", guesser), Some(2));
+    }
+
+    #[test]
+    fn reads_counts_from_a_file() {
+        let path = std::env::temp_dir().join(format!("roget-counts-{}.txt", std::process::id()));
+        fs::write(&path, "hello 3\njello 2\n").unwrap();
+        let loaded = OwnedDictionary::from_file(&path);
+        fs::remove_file(&path).unwrap();
+
+        let (counts, warnings) = loaded.unwrap().counts();
+        assert!(warnings.is_empty());
+        assert_eq!(counts[b"hello"], 3.0);
+        assert_eq!(counts[b"jello"], 2.0);
+    }
+
+    #[test]
+    fn missing_files_are_an_error() {
+        assert!(OwnedDictionary::from_file("/nonexistent/roget.txt").is_err());
+    }
+}
@@ -1,60 +1,212 @@
 extern crate roget;
 
-mod algorithms;
-
-use algorithms::Unoptimized;
-use roget::{RepresentableAsWord, Wordle};
-use std::collections::HashMap;
+use roget::algorithms::{EntropyCache, RandomGuesser, Unoptimized};
+use roget::{
+    describe_state, render_board, word_str, Constraints, Correctness, DictionaryWithCounts, Guess,
+    Guesser, RepresentableAsWord, RunningStats, ScoreDistribution, Wordle, WORD_SIZE,
+};
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
 use std::time::Instant;
 
 const GAMES: &'static str = include_str!("../answers.txt");
 const DICTIONARY: &'static str = include_str!("../dictionary.txt");
 const JOINED: &'static str = include_str!("../joined.txt");
 const GAMES_LENGTH: usize = 2309;
+/// How many won games there are between two prints of the running average.
+const PROGRESS_EVERY: usize = 100;
 
 fn main() {
-    let wordle = Wordle::new(DICTIONARY.lines().map(|word_str| word_str.as_word()));
+    // Only the words in `answers.txt` can be the answer, but any word in the dictionary can be
+    // guessed. The game keeps the counts of `joined.txt`, which guessers take from it.
+    let (wordle, _) = Wordle::builder(JOINED)
+        .dictionary(DICTIONARY)
+        .answers(GAMES)
+        .build()
+        .expect("The word lists should load and agree with each other");
 
-    let initial_remaining = HashMap::from_iter(JOINED.lines().map(|line| {
-        let (word, count) = line
-            .split_once(' ')
-            .expect("Each line should have a word and a count");
-        let word = word.as_word();
-        let count = count.parse().expect("The count should be parse-able");
-        (word, count)
-    }));
+    if std::env::args().nth(1).as_deref() == Some("interactive") {
+        return interactive(&wordle);
+    }
 
     let mut guesses_required = [None; GAMES_LENGTH];
 
     let start = Instant::now();
-    let mut end = Instant::now();
-    for (i, answer) in GAMES.lines().enumerate() {
-        let guesser = Unoptimized::new(wordle.get_dictionary(), initial_remaining.clone());
-        guesses_required[i] = wordle.play(&answer.as_word(), guesser);
 
-        end = Instant::now();
+    // The opener only depends on the dictionary and counts, never on the answer, so we compute it
+    // once here instead of once per game.
+    let opener = Unoptimized::from_wordle(&wordle).guess(&[]);
+    println!(
+        "Picked {} as the opener in {:?}",
+        word_str(opener),
+        start.elapsed()
+    );
+
+    // Every game is independent of the others, so with the `rayon` feature they're spread
+    // over every core. Results still land in `guesses_required` by the index of their answer.
+    let answers = Vec::from_iter(GAMES.lines());
+    let games_played = AtomicUsize::new(0);
+    // The average and standard deviation over the games won so far, which lost games don't count
+    // towards.
+    let stats = Mutex::new(RunningStats::new());
+    // Games that got the same mask for the opener are in the same state for their second guess,
+    // so they only need to score the dictionary for it once between them.
+    let entropy_cache = EntropyCache::new();
+    let guesser = Unoptimized::from_wordle(&wordle)
+        .with_opener(opener)
+        .with_entropy_cache(&entropy_cache);
+    // Each thread plays its games with a guesser of its own, which only has to be reset between
+    // games instead of being cloned for every one of them.
+    let play = |answer: &'static str, guesser: &mut Unoptimized| {
+        guesser.reset();
+        let guesses = match wordle.try_play(answer.as_word(), guesser) {
+            Ok(guesses) => guesses,
+            Err(error) => {
+                eprintln!("Skipping {}: {}", answer, error);
+                None
+            }
+        };
 
         println!(
             "Guessed {} ({} / {}) in {} attempts. Cumulative time: {:?}",
-            &answer,
-            i,
+            answer,
+            games_played.fetch_add(1, Ordering::Relaxed),
             GAMES_LENGTH,
-            guesses_required[i].unwrap_or(0),
-            end.duration_since(start)
+            guesses.unwrap_or(0),
+            start.elapsed()
+        );
+
+        if let Some(guesses) = guesses {
+            let mut stats = stats
+                .lock()
+                .expect("No game should panic while holding the lock");
+            stats.push(guesses);
+            if stats.count().is_multiple_of(PROGRESS_EVERY) {
+                println!(
+                    "Running average after {} wins: {:.4} ± {:.4}",
+                    stats.count(),
+                    stats.mean().unwrap_or(f64::NAN),
+                    stats.stddev().unwrap_or(f64::NAN)
+                );
+            }
+        }
+
+        guesses
+    };
+
+    #[cfg(not(feature = "rayon"))]
+    {
+        let mut guesser = guesser.clone();
+        for (guesses, answer) in guesses_required.iter_mut().zip(&answers) {
+            *guesses = play(answer, &mut guesser);
+        }
+    }
+
+    // Rayon makes a guesser for every batch of games it hands to a thread, and the guesses of
+    // those games score their candidates on the same pool, so games and guesses share one thread
+    // per core between them.
+    #[cfg(feature = "rayon")]
+    {
+        use rayon::prelude::*;
+
+        guesses_required.par_iter_mut().zip(&answers).for_each_init(
+            || guesser.clone(),
+            |guesser, (guesses, answer)| *guesses = play(answer, guesser),
         );
     }
 
+    let end = Instant::now();
+
     // println!("{:?}", guesses_required);
 
+    let stats = stats
+        .into_inner()
+        .expect("No game should panic while holding the lock");
+    // Lost games have no score, so they're counted apart from the average instead of taking it
+    // down with them.
+    let distribution = ScoreDistribution::from_iter(guesses_required);
     println!(
-        "Took {:?} for an average guess score of {}",
+        "Took {:?} for an average guess score of {} over {} wins, with {} lost and {:.2}% won \
+         within 6",
         end.duration_since(start),
-        guesses_required
-            .iter()
-            .map(|item| item.unwrap())
-            .sum::<usize>() as f64
-            / guesses_required.len() as f64
+        stats.mean().unwrap_or(f64::NAN),
+        stats.count(),
+        distribution.losses,
+        100.0 * distribution.win_rate(6).unwrap_or(f64::NAN)
+    );
+    println!("{}", distribution);
+
+    // The random guesser is a cheap baseline for the score above. Seeding each game by its index
+    // keeps the baseline reproducible.
+    let mut seed = 0;
+    let random_distribution = wordle.evaluate(GAMES.lines().map(|answer| answer.as_word()), || {
+        seed += 1;
+        RandomGuesser::new(wordle.get_dictionary(), seed - 1)
+    });
+
+    println!(
+        "The random baseline had an average guess score of {}",
+        random_distribution.mean().unwrap_or(f64::NAN)
     );
 
     ()
 }
+
+/// Helps solve a game that's played elsewhere: suggests a guess, reads the mask the game showed
+/// for it, and repeats until the answer is found. Masks can be typed as letters ("WMCWW"), as
+/// colors ("bygbb"), or pasted as emoji.
+fn interactive(wordle: &Wordle) {
+    let initial_remaining = wordle.counted_answers();
+    let mut guesser = Unoptimized::from_wordle(wordle);
+    let mut past_guesses = Vec::new();
+    let mut line = String::new();
+
+    loop {
+        let word = guesser.guess(&past_guesses);
+        println!("Guess {}", word_str(word));
+
+        let mask = loop {
+            print!("Mask: ");
+            io::stdout().flush().expect("Stdout should be writable");
+
+            line.clear();
+            match io::stdin().read_line(&mut line) {
+                // The input ended before the game did.
+                Ok(0) => {
+                    println!();
+                    return;
+                }
+                Ok(_) => {}
+                Err(error) => {
+                    eprintln!("Could not read the mask: {}", error);
+                    return;
+                }
+            }
+
+            match Correctness::parse_mask(&line) {
+                Ok(mask) => break mask,
+                Err(error) => println!("{}, try again", error),
+            }
+        };
+
+        if mask == [Correctness::Correct; WORD_SIZE] {
+            println!("Solved in {} guesses", past_guesses.len() + 1);
+            return;
+        }
+
+        past_guesses.push(Guess::from_mask(word, mask));
+        let constraints = Constraints::from_guesses(&past_guesses);
+        let remaining: DictionaryWithCounts = initial_remaining
+            .iter()
+            .filter(|(word, _)| constraints.matches(word))
+            .map(|(&word, &count)| (word, count))
+            .collect();
+        if remaining.is_empty() {
+            println!("No possible answer fits those masks");
+            return;
+        }
+        print!("{}", render_board(&past_guesses));
+        println!("{}", describe_state(&remaining));
+    }
+}
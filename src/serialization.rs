@@ -0,0 +1,236 @@
+//! Serializes masks and guesses through serde, behind the `serde` feature, for saving games and
+//! reading masks from fixtures. Everything is written the way it reads in text: `Correctness` as
+//! its letter, "C", "M" or "W", and a `Guess` as its word, spelled out, and its mask.
+//!
+//! A deserialized `Guess` borrows its word from the input, like every other `Guess` borrows its
+//! word from a dictionary, so it can only be read from input that outlives it, like a `&str`.
+
+use crate::{Correctness, Guess, Word};
+use serde::de::{self, Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeStruct, SerializeTuple, Serializer};
+use std::fmt;
+
+impl Serialize for Correctness {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_char(self.as_letter())
+    }
+}
+
+impl<'de> Deserialize<'de> for Correctness {
+    /// Takes any letter `Correctness::from_letter` does, so masks can be written by hand as
+    /// colors too.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct LetterVisitor;
+
+        impl Visitor<'_> for LetterVisitor {
+            type Value = Correctness;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "one of the letters C, M or W")
+            }
+
+            fn visit_char<E: de::Error>(self, letter: char) -> Result<Correctness, E> {
+                Correctness::from_letter(letter).map_err(E::custom)
+            }
+
+            fn visit_str<E: de::Error>(self, letter: &str) -> Result<Correctness, E> {
+                let mut chars = letter.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(letter), None) => self.visit_char(letter),
+                    _ => Err(E::invalid_value(de::Unexpected::Str(letter), &self)),
+                }
+            }
+        }
+
+        deserializer.deserialize_char(LetterVisitor)
+    }
+}
+
+/// The mask of a guess, which serde only knows how to handle as an array for word sizes it lists.
+struct Mask<const N: usize>([Correctness; N]);
+
+impl<const N: usize> Serialize for Mask<N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut mask = serializer.serialize_tuple(N)?;
+        for correctness in &self.0 {
+            mask.serialize_element(correctness)?;
+        }
+        mask.end()
+    }
+}
+
+impl<'de, const N: usize> Deserialize<'de> for Mask<N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct MaskVisitor<const N: usize>;
+
+        impl<'de, const N: usize> Visitor<'de> for MaskVisitor<N> {
+            type Value = Mask<N>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "a mask of {} letters", N)
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Mask<N>, A::Error> {
+                let mut mask = [Correctness::Wrong; N];
+                for (i, correctness) in mask.iter_mut().enumerate() {
+                    *correctness = seq
+                        .next_element()?
+                        .ok_or_else(|| de::Error::invalid_length(i, &self))?;
+                }
+
+                Ok(Mask(mask))
+            }
+        }
+
+        deserializer.deserialize_tuple(N, MaskVisitor)
+    }
+}
+
+/// A word borrowed from the input of a deserializer.
+struct BorrowedWord<'de, const N: usize>(&'de Word<N>);
+
+impl<'de, const N: usize> Deserialize<'de> for BorrowedWord<'de, N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct WordVisitor<const N: usize>;
+
+        impl<'de, const N: usize> Visitor<'de> for WordVisitor<N> {
+            type Value = BorrowedWord<'de, N>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(
+                    f,
+                    "a word of {} ASCII characters borrowed from the input",
+                    N
+                )
+            }
+
+            fn visit_borrowed_str<E: de::Error>(
+                self,
+                word: &'de str,
+            ) -> Result<BorrowedWord<'de, N>, E> {
+                if !word.is_ascii() {
+                    return Err(E::invalid_value(de::Unexpected::Str(word), &self));
+                }
+
+                word.as_bytes()
+                    .try_into()
+                    .map(BorrowedWord)
+                    .map_err(|_| E::invalid_length(word.len(), &self))
+            }
+        }
+
+        deserializer.deserialize_str(WordVisitor)
+    }
+}
+
+/// Written as a struct of the word, as a string, and the mask, e.g. `{"word":"crane",
+/// "mask":["W","M","C","W","W"]}` in JSON.
+impl<const N: usize> Serialize for Guess<'_, N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let word = std::str::from_utf8(self.word).map_err(serde::ser::Error::custom)?;
+
+        let mut guess = serializer.serialize_struct("Guess", 2)?;
+        guess.serialize_field("word", word)?;
+        guess.serialize_field("mask", &Mask(self.mask))?;
+        guess.end()
+    }
+}
+
+impl<'de: 'w, 'w, const N: usize> Deserialize<'de> for Guess<'w, N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct GuessVisitor<const N: usize>;
+
+        impl<'de, const N: usize> Visitor<'de> for GuessVisitor<N> {
+            type Value = Guess<'de, N>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "a guess with a word and a mask")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Guess<'de, N>, A::Error> {
+                let BorrowedWord(word) = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                let Mask(mask) = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+
+                Ok(Guess::from_mask(word, mask))
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Guess<'de, N>, A::Error> {
+                let mut word = None;
+                let mut mask = None;
+
+                while let Some(key) = map.next_key::<String>()? {
+                    match key.as_str() {
+                        "word" if word.is_some() => return Err(de::Error::duplicate_field("word")),
+                        "mask" if mask.is_some() => return Err(de::Error::duplicate_field("mask")),
+                        "word" => word = Some(map.next_value::<BorrowedWord<N>>()?.0),
+                        "mask" => mask = Some(map.next_value::<Mask<N>>()?.0),
+                        key => return Err(de::Error::unknown_field(key, FIELDS)),
+                    }
+                }
+
+                let word = word.ok_or_else(|| de::Error::missing_field("word"))?;
+                let mask = mask.ok_or_else(|| de::Error::missing_field("mask"))?;
+
+                Ok(Guess::from_mask(word, mask))
+            }
+        }
+
+        const FIELDS: &[&str] = &["word", "mask"];
+        deserializer.deserialize_struct("Guess", FIELDS, GuessVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod serialization {
+        use crate::{Correctness, Guess};
+
+        #[test]
+        fn masks_are_written_as_letters() {
+            let json =
+                serde_json::to_string(&[Correctness::Correct, Correctness::Misplaced]).unwrap();
+            assert_eq!(json, r#"["C","M"]"#);
+
+            let mask: Vec<Correctness> = serde_json::from_str(r#"["W","y","G"]"#).unwrap();
+            assert_eq!(
+                mask,
+                [
+                    Correctness::Wrong,
+                    Correctness::Misplaced,
+                    Correctness::Correct
+                ]
+            );
+            assert!(serde_json::from_str::<Correctness>(r#""X""#).is_err());
+            assert!(serde_json::from_str::<Correctness>(r#""CM""#).is_err());
+        }
+
+        #[test]
+        fn guesses_round_trip() {
+            let guess = Guess::new(b"tread", b"crane");
+            let json = serde_json::to_string(&guess).unwrap();
+            assert_eq!(json, r#"{"word":"crane","mask":["W","C","M","W","M"]}"#);
+
+            let read: Guess = serde_json::from_str(&json).unwrap();
+            assert_eq!(read, guess);
+
+            let guesses = [guess, Guess::new(b"tread", b"tread")];
+            let json = serde_json::to_string(&guesses).unwrap();
+            assert_eq!(serde_json::from_str::<Vec<Guess>>(&json).unwrap(), guesses);
+        }
+
+        #[test]
+        fn words_have_to_be_the_right_length() {
+            let short = r#"{"word":"cran","mask":["W","W","W","W","W"]}"#;
+            let error = serde_json::from_str::<Guess>(short).unwrap_err();
+            assert!(error.to_string().contains("invalid length 4"));
+
+            let long_mask = r#"{"word":"crane","mask":["W","W","W","W","W","W"]}"#;
+            assert!(serde_json::from_str::<Guess>(long_mask).is_err());
+            assert!(serde_json::from_str::<Guess>(r#"{"word":"crane"}"#).is_err());
+        }
+    }
+}
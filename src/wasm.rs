@@ -0,0 +1,129 @@
+//! A solver entry point that only deals in strings, for running the crate in a browser, which
+//! can't load word lists from disk. The word lists are compiled in, and `best_guess` is exported
+//! through wasm-bindgen, which is why this is behind the `wasm` feature. Build it for the browser
+//! with `cargo rustc --lib --release --target wasm32-unknown-unknown --features wasm --crate-type
+//! cdylib`, and run the result through `wasm-bindgen`.
+
+use crate::algorithms::Unoptimized;
+use crate::{
+    word_str, Dictionary, DictionaryLoader, DictionaryWithCounts, Guess, Guesser, WordDisplay,
+    Wordle,
+};
+use std::sync::OnceLock;
+use wasm_bindgen::prelude::wasm_bindgen;
+
+const DICTIONARY: &str = include_str!("../dictionary.txt");
+const ANSWERS: &str = include_str!("../answers.txt");
+const JOINED: &str = include_str!("../joined.txt");
+
+/// The game of the compiled in word lists, built by the first call to `best_guess` and shared by
+/// every call after it, since parsing the lists takes longer than most guesses.
+fn wordle() -> &'static Wordle<'static> {
+    static WORDLE: OnceLock<Wordle<'static>> = OnceLock::new();
+
+    WORDLE.get_or_init(|| {
+        let (dictionary, _) = DictionaryLoader::from_wordlist(DICTIONARY);
+        let (answers, _) = DictionaryLoader::from_wordlist(ANSWERS);
+        let (counts, _) = DictionaryLoader::from_counts(JOINED);
+
+        Wordle::with_answers(
+            dictionary,
+            counts
+                .into_iter()
+                .filter(|(word, _)| answers.contains(word))
+                .collect(),
+        )
+    })
+}
+
+/// Suggests the next word to guess. `past_guesses_json` is a JSON array of the guesses so far,
+/// written the way the `serde` feature writes a `Guess`, like
+/// `[{"word":"crane","mask":["W","M","C","W","W"]}]`.
+///
+/// Fails with a readable message if the guesses can't be parsed or name a word that isn't in the
+/// dictionary, which JavaScript gets as a thrown string.
+#[wasm_bindgen]
+pub fn best_guess(past_guesses_json: &str) -> Result<String, String> {
+    let wordle = wordle();
+    let past = serde_json::from_str::<Vec<Guess>>(past_guesses_json)
+        .map_err(|error| error.to_string())?
+        .into_iter()
+        .map(|guess| in_dictionary(guess, wordle.get_dictionary()))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // `Unoptimized` only narrows its answers down by the latest guess, so we narrow them by all
+    // of the past guesses up front instead, and let it pick as if it were the first guess.
+    let remaining: DictionaryWithCounts =
+        Guess::filter_candidates(&past, wordle.get_possible_answers().keys())
+            .into_iter()
+            .map(|word| (word, wordle.get_possible_answers()[word]))
+            .collect();
+    if remaining.is_empty() {
+        return Err(String::from("no possible answer fits the past guesses"));
+    }
+    let word = Unoptimized::new(wordle.get_dictionary(), remaining).guess(&[]);
+
+    Ok(word_str(word).to_owned())
+}
+
+/// The same guess, with its word borrowed from `dictionary` instead of from the input.
+fn in_dictionary<'w>(guess: Guess, dictionary: &Dictionary<'w>) -> Result<Guess<'w>, String> {
+    let &word = dictionary
+        .get(guess.word)
+        .ok_or_else(|| format!("{} is not in the dictionary", WordDisplay(guess.word)))?;
+
+    Ok(Guess::from_mask(word, guess.mask))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::wasm::{best_guess, wordle};
+    use crate::Guess;
+
+    /// The guesses of a game of "rebut", written the way `best_guess` reads them.
+    fn rebut_json() -> String {
+        let past = [
+            Guess::new(b"rebut", b"crane"),
+            Guess::new(b"rebut", b"sloth"),
+            Guess::new(b"rebut", b"dumpy"),
+            Guess::new(b"rebut", b"bewig"),
+        ];
+
+        serde_json::to_string(&past).unwrap()
+    }
+
+    #[test]
+    fn narrows_down_to_the_answer() {
+        assert_eq!(best_guess(&rebut_json()), Ok(String::from("rebut")));
+    }
+
+    #[test]
+    fn accepts_masks_written_as_colors() {
+        assert_eq!(
+            best_guess(r#"[{"word":"crane","mask":["b","y","b","b","y"]}]"#),
+            best_guess(r#"[{"word":"crane","mask":["W","M","W","W","M"]}]"#)
+        );
+    }
+
+    #[test]
+    fn the_game_is_built_once() {
+        assert!(std::ptr::eq(wordle(), wordle()));
+        assert_eq!(best_guess("[]"), best_guess("[]"));
+    }
+
+    #[test]
+    fn rejects_words_outside_the_dictionary() {
+        assert_eq!(
+            best_guess(r#"[{"word":"zzzzz","mask":["W","W","W","W","W"]}]"#),
+            Err(String::from("zzzzz is not in the dictionary"))
+        );
+        assert!(best_guess(r#"[{"word":"crane","mask":["W","M","W"]}]"#).is_err());
+        assert!(best_guess("crane WMWWM").is_err());
+
+        let solved_twice = r#"[
+            {"word":"crane","mask":["C","C","C","C","C"]},
+            {"word":"slate","mask":["C","C","C","C","C"]}
+        ]"#;
+        assert!(best_guess(solved_twice).is_err());
+    }
+}
@@ -0,0 +1,54 @@
+//! Pins how well `Unoptimized` plays, since nothing else catches a change that makes it guess
+//! worse. It's ignored by default, so run it with
+//! `cargo test --release --test average_score -- --ignored`.
+
+use roget::algorithms::{EntropyCache, Unoptimized};
+use roget::{Guesser, RepresentableAsWord, Wordle};
+
+const DICTIONARY: &str = include_str!("../dictionary.txt");
+const ANSWERS: &str = include_str!("../answers.txt");
+const JOINED: &str = include_str!("../joined.txt");
+
+/// Every 46th answer, which spreads 50 games over all of `answers.txt`.
+const GAMES: usize = 50;
+const STEP: usize = 46;
+
+/// The average was 3.48 when this was written, and the test took about 40 seconds in a debug
+/// build on one core. A bit of slack lets tie-breaks between guesses that are just as good move,
+/// without letting a real regression through.
+const MAX_AVERAGE: f64 = 3.6;
+
+#[test]
+#[ignore]
+fn unoptimized_averages_below_the_baseline() {
+    let (wordle, remaining) = Wordle::builder(JOINED)
+        .dictionary(DICTIONARY)
+        .answers(ANSWERS)
+        .build()
+        .expect("The word lists should load and agree with each other");
+
+    // Picking the opener is the slowest guess by far, and it's the same for every game, so it's
+    // picked once and shared, just like `main.rs` does.
+    let entropy_cache = EntropyCache::new();
+    let opener = Unoptimized::new(wordle.get_dictionary(), remaining.clone()).guess(&[]);
+    let mut guesser = Unoptimized::new(wordle.get_dictionary(), remaining)
+        .with_opener(opener)
+        .with_entropy_cache(&entropy_cache);
+
+    let mut total = 0;
+    for answer in ANSWERS.lines().step_by(STEP).take(GAMES) {
+        guesser.reset();
+        total += wordle
+            .play(answer.as_word(), &mut guesser)
+            .unwrap_or_else(|| panic!("Unoptimized should solve {}", answer));
+    }
+
+    let average = total as f64 / GAMES as f64;
+    assert!(
+        average < MAX_AVERAGE,
+        "The average was {}, above {}",
+        average,
+        MAX_AVERAGE
+    );
+    println!("The average was {}", average);
+}
@@ -0,0 +1,3 @@
+fn main() {
+    let _: &roget::Word = roget::word!("héll");
+}
@@ -0,0 +1,23 @@
+// Plays the end of a game of "rebut" through the wasm build, see smoke.sh.
+const assert = require("node:assert");
+const { best_guess } = require("../../target/wasm-smoke/roget.js");
+
+const guess = (word, mask) => ({ word, mask: [...mask] });
+
+assert.strictEqual(
+  best_guess(
+    JSON.stringify([
+      guess("crane", "WMWWM"),
+      guess("sloth", "WWWMW"),
+      guess("dumpy", "WMWWW"),
+      guess("bewig", "MCWWW"),
+    ])
+  ),
+  "rebut"
+);
+assert.throws(
+  () => best_guess(JSON.stringify([guess("zzzzz", "WWWWW")])),
+  (error) => error === "zzzzz is not in the dictionary"
+);
+
+console.log("The wasm build guesses in node");
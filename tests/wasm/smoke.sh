@@ -0,0 +1,11 @@
+#!/bin/sh
+# Builds the `wasm` feature for wasm32, binds it with wasm-bindgen, and asks it for a guess from
+# node. Needs the wasm32-unknown-unknown target, and a wasm-bindgen of the same version as the
+# wasm-bindgen crate in Cargo.lock.
+set -e
+cd "$(dirname "$0")/../.."
+
+cargo rustc --lib --release --target wasm32-unknown-unknown --features wasm --crate-type cdylib
+wasm-bindgen --target nodejs --out-dir target/wasm-smoke \
+    target/wasm32-unknown-unknown/release/roget.wasm
+node tests/wasm/smoke.js
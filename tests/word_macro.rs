@@ -0,0 +1,9 @@
+//! Checks that `word!` rejects literals that aren't words at compile time, which only a separate
+//! compilation can show. The expected errors are in `tests/ui`, next to the code that causes them.
+
+#[test]
+fn literals_that_are_not_words_do_not_compile() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/word_too_short.rs");
+    cases.compile_fail("tests/ui/word_not_ascii.rs");
+}